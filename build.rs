@@ -0,0 +1,5 @@
+// Declares the cfgs Anchor 0.29's macros expand to, so rustc's check-cfg accepts them.
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(feature, values(\"anchor-debug\", \"custom-heap\", \"custom-panic\"))");
+    println!("cargo:rustc-check-cfg=cfg(target_os, values(\"solana\"))");
+}
@@ -8,13 +8,43 @@ pub const TOKEN_POOL_SEED: &[u8] = b"token_pool";
 pub const USER_PREFERENCES_SEED: &[u8] = b"user_preferences";
 pub const GLOBAL_POOLS_SEED: &[u8] = b"global_pools";
 pub const DISTRIBUTION_CONFIG_SEED: &[u8] = b"distribution_config";
+pub const DISTRIBUTION_EPOCH_SEED: &[u8] = b"distribution_epoch";
+pub const HOLDER_REWARDS_SEED: &[u8] = b"holder_rewards";
+pub const REWARD_ASSET_SEED: &[u8] = b"reward_asset";
+pub const RENT_RESERVE_SEED: &[u8] = b"rent_reserve";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const PENDING_CHANGE_SEED: &[u8] = b"pending_change";
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
 
 // Maximum sizes
 pub const MAX_BLOCKLIST_SIZE: usize = 100;
+pub const MAX_DEPOSITORS: usize = 20;
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+pub const MAX_ROLE_GRANTS: usize = 32;
+pub const MAX_REWARD_ASSETS: u8 = 4;
+pub const MAX_PENDING_REFLECTIONS: usize = 4;
+pub const MAX_CYCLE_SNAPSHOTS: usize = 8;
 pub const MAX_MEMO_LENGTH: usize = 200;
+pub const MAX_EPOCH_LEAVES: u32 = 8192; // 1024-byte claim bitmap
+pub const MAX_MERKLE_PROOF_LENGTH: usize = 32;
+
+// Seconds a permissionless crank must wait before it may reset the reflect cursor
+pub const CRANK_RESET_DELAY: i64 = 3600;
+
+// Upper bound for Config.timelock_delay (30 days)
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 3600;
+
+// Seconds a Proposal stays executable after creation (7 days)
+pub const PROPOSAL_LIFETIME: i64 = 7 * 24 * 3600;
+
+// Fixed-point scale for DistributionConfig.acc_reward_per_share
+pub const ACC_REWARD_PRECISION: u128 = 1_000_000_000_000;
 
 // Authority pubkey (to be set during initialization)
 pub const DEFAULT_AUTHORITY: &str = "YourAuthorityPubkeyHere111111111111111111111111";
 
 // Program version
-pub const PROGRAM_VERSION: u8 = 1;
\ No newline at end of file
+pub const PROGRAM_VERSION: u8 = 1;
+
+// Config layout version written by initialize and migrate_config (1 = before the appended fields)
+pub const CONFIG_VERSION: u8 = 2;
\ No newline at end of file
@@ -50,4 +50,106 @@ pub enum SolFlexError {
     #[msg("No eligible accounts in this batch")]
     NoEligibleAccounts,
 
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Reflection already claimed")]
+    AlreadyClaimed,
+
+    #[msg("Invalid swap route")]
+    InvalidSwapRoute,
+
+    #[msg("Swap output below minimum")]
+    SlippageExceeded,
+
+    #[msg("Depositor already allowlisted")]
+    DepositorAlreadyAllowed,
+
+    #[msg("Depositor not allowlisted")]
+    DepositorNotAllowed,
+
+    #[msg("Depositor allowlist is full")]
+    DepositorAllowlistFull,
+
+    #[msg("Distribution halted: fee vault does not cover tracked pools")]
+    DistributionHalted,
+
+    #[msg("Reward asset limit reached")]
+    RewardAssetLimitReached,
+
+    #[msg("Reward asset still holds undistributed rewards")]
+    RewardAssetNotEmpty,
+
+    #[msg("Rent spent creating accounts exceeds the per-batch cap")]
+    RentBudgetExceeded,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Invalid multisig signer set or threshold")]
+    InvalidMultisig,
+
+    #[msg("Signer is not in the multisig signer set")]
+    NotMultisigSigner,
+
+    #[msg("An approved proposal is required while a multisig is configured")]
+    ProposalRequired,
+
+    #[msg("Proposal does not match this instruction or its parameters")]
+    ProposalMismatch,
+
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Signer already approved this proposal")]
+    AlreadyApproved,
+
+    #[msg("Role already granted")]
+    RoleAlreadyGranted,
+
+    #[msg("Role not granted")]
+    RoleNotGranted,
+
+    #[msg("Role grant list is full")]
+    RoleGrantsFull,
+
+    #[msg("Change must be queued through the timelock")]
+    TimelockRequired,
+
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Proposal was created under a replaced signer set")]
+    ProposalStale,
+
+    #[msg("Proposal can still be executed")]
+    ProposalStillOpen,
+
+    #[msg("Accumulator shares or owed rewards are still outstanding")]
+    SharesOutstanding,
+
+    #[msg("Claim window has closed")]
+    ClaimWindowClosed,
+
+    #[msg("Claim window is still open")]
+    ClaimWindowOpen,
+
+    #[msg("Account must be migrated to the current layout first")]
+    AccountNotMigrated,
+
+    #[msg("Account is already at the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Holders must be supplied in holder_index order from the cycle cursor")]
+    HolderOutOfOrder,
+
 }
\ No newline at end of file
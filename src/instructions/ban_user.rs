@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Config, UserPreferences};
+use crate::state::{Config, DistributionConfig, HolderRewards, PauseScope, Role, UserPreferences};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct BanUserParams {
@@ -12,33 +12,47 @@ pub struct BanUserParams {
 pub struct BanUser<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
     #[account(
         mut,
         seeds = [UserPreferences::SEED_PREFIX, params.user_to_ban.as_ref()],
-        bump
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub user_preferences: Account<'info, UserPreferences>,
 
+    /// Accumulator distribution the user stakes in (pass it with `holder_rewards`), or a
+    /// reflect distribution whose running cycle should stop counting the user.
+    #[account(mut)]
+    pub distribution_config: Option<Account<'info, DistributionConfig>>,
+
+    /// The user's stake checkpoint in `distribution_config`, re-checkpointed on ban and unban.
+    #[account(mut)]
+    pub holder_rewards: Option<Account<'info, HolderRewards>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<BanUser>, params: BanUserParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Preferences)?;
+
     let config = &ctx.accounts.config;
     let user_preferences = &mut ctx.accounts.user_preferences;
     let authority = &ctx.accounts.authority;
 
-    // Check authorization - only the config authority or the user themselves (for banning only)
+    // Check authorization - only a blocklist manager or the user themselves (for banning only)
+    let is_manager = config.has_role(authority.key(), Role::BlocklistManager);
     let is_authorized = if params.ban_status {
         // Anyone can ban themselves
-        authority.key() == params.user_to_ban || authority.key() == config.authority
+        authority.key() == params.user_to_ban || is_manager
     } else {
-        // Only authority can unban
-        authority.key() == config.authority
+        // Only a blocklist manager can unban
+        is_manager
     };
 
     require!(is_authorized, crate::errors::SolFlexError::Unauthorized);
@@ -47,6 +61,29 @@ pub fn handler(ctx: Context<BanUser>, params: BanUserParams) -> Result<()> {
     user_preferences.is_banned = params.ban_status;
     user_preferences.updated_at = Clock::get().unwrap().unix_timestamp;
 
+    // Settle what the stake earned so far and stop (or resume) its share of new rewards.
+    match (ctx.accounts.distribution_config.as_mut(), ctx.accounts.holder_rewards.as_mut()) {
+        (Some(distribution_config), Some(holder_rewards)) => {
+            require!(
+                holder_rewards.owner == params.user_to_ban && holder_rewards.distribution == distribution_config.key(),
+                crate::errors::SolFlexError::InvalidParameters
+            );
+            let eligible = !params.ban_status && !config.is_blocklisted(params.user_to_ban);
+            let shares = if eligible { holder_rewards.staked } else { 0 };
+            distribution_config.checkpoint_shares(holder_rewards, shares)?;
+            holder_rewards.updated_at = user_preferences.updated_at;
+        }
+        // A banned holder stops counting toward the distribution's running reflect cycle.
+        (Some(distribution_config), None) => {
+            if params.ban_status {
+                let distribution = distribution_config.key();
+                distribution_config.release_holder(distribution, user_preferences)?;
+            }
+        }
+        (None, None) => {}
+        (None, Some(_)) => return err!(crate::errors::SolFlexError::InvalidParameters),
+    }
+
     msg!("User {} ban status set to {}", params.user_to_ban, params.ban_status);
 
     Ok(())
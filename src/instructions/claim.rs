@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, DistributionConfig, DistributionMode, HolderRewards, PauseScope, Role, UserPreferences};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        seeds = [UserPreferences::SEED_PREFIX, user_preferences.owner.as_ref()],
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub user_preferences: Account<'info, UserPreferences>,
+
+    #[account(
+        mut,
+        seeds = [
+            HolderRewards::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            user_preferences.owner.as_ref()
+        ],
+        bump = holder_rewards.bump
+    )]
+    pub holder_rewards: Account<'info, HolderRewards>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user_preferences.owner @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<Claim>) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let user_preferences = &ctx.accounts.user_preferences;
+    let holder_rewards = &mut ctx.accounts.holder_rewards;
+    let owner = user_preferences.owner;
+
+    // Either the user or a cranker can claim; funds always go to the user's account.
+    require!(
+        ctx.accounts.authority.key() == owner || config.has_role(ctx.accounts.authority.key(), Role::Cranker),
+        crate::errors::SolFlexError::Unauthorized
+    );
+    require!(
+        distribution_config.distribution_mode == DistributionMode::Accumulator,
+        crate::errors::SolFlexError::InvalidConfig
+    );
+
+    // Shares come from the stake, not the wallet balance. Banned or blocklisted users stop
+    // earning (their shares are checkpointed to zero) and earn again once reinstated.
+    let eligible = !user_preferences.is_banned && !config.is_blocklisted(owner);
+    let new_shares = if eligible { holder_rewards.staked } else { 0 };
+    distribution_config.checkpoint_shares(holder_rewards, new_shares)?;
+
+    let payout = if eligible { holder_rewards.pending_rewards } else { 0 };
+    if payout > 0 {
+        let token_mint_key = distribution_config.token_mint;
+        let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: distribution_config.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &[signer_seeds],
+            ),
+            payout,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        distribution_config.pay_accrued(payout)?;
+        holder_rewards.pending_rewards = 0;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    distribution_config.updated_at = now;
+    holder_rewards.updated_at = now;
+
+    msg!("Claimed {} for {}, shares={}", payout, owner, new_shares);
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, DistributionConfig, DistributionEpoch, PauseScope, UserPreferences};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimReflectionParams {
+    pub epoch: u64,
+    pub index: u32,
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: ClaimReflectionParams)]
+pub struct ClaimReflection<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        seeds = [DistributionEpoch::SEED_PREFIX, distribution_config.key().as_ref(), params.epoch.to_le_bytes().as_ref()],
+        bump = distribution_epoch.bump
+    )]
+    pub distribution_epoch: Account<'info, DistributionEpoch>,
+
+    #[account(
+        seeds = [UserPreferences::SEED_PREFIX, claimant.key().as_ref()],
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub user_preferences: Account<'info, UserPreferences>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = claimant_token_account.owner == claimant.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub claimant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub claimant: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ClaimReflection>, params: ClaimReflectionParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let distribution_epoch = &mut ctx.accounts.distribution_epoch;
+    let claimant = ctx.accounts.claimant.key();
+
+    require!(
+        !ctx.accounts.user_preferences.is_banned && !config.is_blocklisted(claimant),
+        crate::errors::SolFlexError::Unauthorized
+    );
+    require!(
+        distribution_epoch.is_open(Clock::get()?.unix_timestamp),
+        crate::errors::SolFlexError::ClaimWindowClosed
+    );
+    require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
+    require!(
+        params.proof.len() <= crate::constants::MAX_MERKLE_PROOF_LENGTH,
+        crate::errors::SolFlexError::InvalidMerkleProof
+    );
+
+    let leaf = DistributionEpoch::leaf(params.index, &claimant, params.amount);
+    require!(
+        distribution_epoch.verify_proof(&params.proof, leaf),
+        crate::errors::SolFlexError::InvalidMerkleProof
+    );
+    distribution_epoch.set_claimed(params.index)?;
+
+    let claimed_amount = distribution_epoch
+        .claimed_amount
+        .checked_add(params.amount)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+    require!(
+        claimed_amount <= distribution_epoch.total_amount,
+        crate::errors::SolFlexError::InsufficientFunds
+    );
+    distribution_epoch.claimed_amount = claimed_amount;
+
+    let token_mint_key = distribution_config.token_mint;
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.fee_vault.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.claimant_token_account.to_account_info(),
+        authority: distribution_config.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[signer_seeds],
+        ),
+        params.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    distribution_config.claims_pool = distribution_config
+        .claims_pool
+        .checked_sub(params.amount)
+        .ok_or(crate::errors::SolFlexError::InsufficientFunds)?;
+    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Claimed {} from epoch {} (index {}) for {}",
+        params.amount,
+        params.epoch,
+        params.index,
+        claimant
+    );
+    Ok(())
+}
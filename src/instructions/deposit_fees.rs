@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::instructions::record_fees::burn_pending;
+use crate::state::{Config, DistributionConfig, PauseScope};
+use crate::token_utils::token_account_amount;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DepositFeesParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DepositFeesParams)]
+pub struct DepositFees<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = depositor_token_account.owner == depositor.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub depositor: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<DepositFees>, params: DepositFeesParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Fees)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let fee_vault = ctx.accounts.fee_vault.to_account_info();
+
+    require!(
+        config.is_depositor(ctx.accounts.depositor.key()),
+        crate::errors::SolFlexError::DepositorNotAllowed
+    );
+    require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
+
+    let vault_before = token_account_amount(&fee_vault)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: fee_vault.clone(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        params.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // Record what actually reached the vault (net of any Token-2022 transfer fee).
+    let received = token_account_amount(&fee_vault)?.saturating_sub(vault_before);
+    require!(received > 0, crate::errors::SolFlexError::InsufficientFunds);
+    let split = distribution_config.record_fees(received)?;
+    burn_pending(
+        distribution_config,
+        fee_vault,
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Deposited {} ({} received) from {} => reflection={}, burn={}, project={}, dev={}",
+        params.amount,
+        received,
+        ctx.accounts.depositor.key(),
+        split.reflection,
+        split.burn,
+        split.project,
+        split.dev
+    );
+    Ok(())
+}
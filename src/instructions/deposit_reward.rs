@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, DistributionConfig, PauseScope, RewardAsset, RewardAssetKind};
+use crate::token_utils::token_account_amount;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DepositRewardParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DepositRewardParams)]
+pub struct DepositReward<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            RewardAsset::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            reward_asset.reward_mint.as_ref()
+        ],
+        bump = reward_asset.bump,
+        constraint = reward_asset.kind == RewardAssetKind::Token @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub reward_asset: Account<'info, RewardAsset>,
+
+    #[account(
+        mut,
+        constraint = reward_vault.key() == reward_asset.vault @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = reward_mint.key() == reward_asset.reward_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == reward_asset.reward_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = depositor_token_account.owner == depositor.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub depositor: Signer<'info>,
+
+    #[account(
+        constraint = token_program.key() == reward_asset.token_program @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<DepositReward>, params: DepositRewardParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Fees)?;
+
+    let config = &ctx.accounts.config;
+    let reward_asset = &mut ctx.accounts.reward_asset;
+    let reward_vault = ctx.accounts.reward_vault.to_account_info();
+
+    require!(
+        config.is_depositor(ctx.accounts.depositor.key()),
+        crate::errors::SolFlexError::DepositorNotAllowed
+    );
+    require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
+
+    let vault_before = token_account_amount(&reward_vault)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: reward_vault.clone(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        params.amount,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+
+    // Basket assets are reflected in full; only the base token is split and burned.
+    let received = token_account_amount(&reward_vault)?.saturating_sub(vault_before);
+    require!(received > 0, crate::errors::SolFlexError::InsufficientFunds);
+    reward_asset.credit(received)?;
+
+    msg!(
+        "Deposited {} ({} received) of reward asset {} from {}",
+        params.amount,
+        received,
+        reward_asset.reward_mint,
+        ctx.accounts.depositor.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::{Config, DistributionConfig, PauseScope, RewardAsset, RewardAssetKind};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DepositSolRewardParams {
+    pub amount: u64, // Lamports
+}
+
+#[derive(Accounts)]
+#[instruction(params: DepositSolRewardParams)]
+pub struct DepositSolReward<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            RewardAsset::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            reward_asset.reward_mint.as_ref()
+        ],
+        bump = reward_asset.bump,
+        constraint = reward_asset.kind == RewardAssetKind::NativeSol @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub reward_asset: Account<'info, RewardAsset>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositSolReward>, params: DepositSolRewardParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Fees)?;
+
+    let config = &ctx.accounts.config;
+
+    require!(
+        config.is_depositor(ctx.accounts.depositor.key()),
+        crate::errors::SolFlexError::DepositorNotAllowed
+    );
+    require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.reward_asset.to_account_info(),
+            },
+        ),
+        params.amount,
+    )?;
+
+    let reward_asset = &mut ctx.accounts.reward_asset;
+    reward_asset.credit(params.amount)?;
+
+    msg!(
+        "Deposited {} lamports of SOL rewards from {}",
+        params.amount,
+        ctx.accounts.depositor.key()
+    );
+    Ok(())
+}
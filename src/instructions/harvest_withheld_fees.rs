@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction::{
+    harvest_withheld_tokens_to_mint, withdraw_withheld_tokens_from_mint,
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::instructions::record_fees::burn_pending;
+use crate::state::{Config, DistributionConfig, PauseScope, Role};
+use crate::token_utils::{token_account_amount, withdraw_withheld_authority};
+
+#[derive(Accounts)]
+pub struct HarvestWithheldFees<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestWithheldFees<'info>>) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Fees)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let token_mint = ctx.accounts.token_mint.to_account_info();
+    let fee_vault = ctx.accounts.fee_vault.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    require!(
+        config.has_role(ctx.accounts.authority.key(), Role::FeeRecorder),
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    // The distribution_config PDA must be the mint's withdraw-withheld authority.
+    require!(
+        withdraw_withheld_authority(&token_mint)? == Some(distribution_config.key()),
+        crate::errors::SolFlexError::InvalidConfig
+    );
+
+    // Remaining accounts: holder token accounts whose withheld fees are harvested to the mint.
+    if !ctx.remaining_accounts.is_empty() {
+        let sources: Vec<&Pubkey> = ctx.remaining_accounts.iter().map(|info| info.key).collect();
+        let harvest_ix = harvest_withheld_tokens_to_mint(&token_program.key(), &token_mint.key(), &sources)?;
+        let mut harvest_accounts = vec![token_mint.clone()];
+        harvest_accounts.extend(ctx.remaining_accounts.iter().cloned());
+        invoke(&harvest_ix, &harvest_accounts)?;
+    }
+
+    let vault_before = token_account_amount(&fee_vault)?;
+    let token_mint_key = distribution_config.token_mint;
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+    let withdraw_ix = withdraw_withheld_tokens_from_mint(
+        &token_program.key(),
+        &token_mint.key(),
+        &fee_vault.key(),
+        &distribution_config.key(),
+        &[],
+    )?;
+    invoke_signed(
+        &withdraw_ix,
+        &[token_mint.clone(), fee_vault.clone(), distribution_config.to_account_info()],
+        &[signer_seeds],
+    )?;
+
+    // Account for what actually reached the vault, then split it like record_fees.
+    let received = token_account_amount(&fee_vault)?.saturating_sub(vault_before);
+    require!(received > 0, crate::errors::SolFlexError::InsufficientFunds);
+    let split = distribution_config.record_fees(received)?;
+    burn_pending(distribution_config, fee_vault, token_mint, token_program)?;
+
+    msg!(
+        "Harvested {} withheld fees from {} accounts => reflection={}, burn={}, project={}, dev={}",
+        received,
+        ctx.remaining_accounts.len(),
+        split.reflection,
+        split.burn,
+        split.project,
+        split.dev
+    );
+    Ok(())
+}
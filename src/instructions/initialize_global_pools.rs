@@ -5,7 +5,8 @@ use crate::state::{Config, GlobalTokenPools};
 pub struct InitializeGlobalPools<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
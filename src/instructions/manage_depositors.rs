@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddDepositorParams {
+    pub depositor: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct AddDepositor<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.has_role(authority.key(), Role::Admin) @ crate::errors::SolFlexError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn add_depositor_handler(ctx: Context<AddDepositor>, params: AddDepositorParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.add_depositor(params.depositor)?;
+
+    msg!("Depositor {} allowlisted by: {}", params.depositor, ctx.accounts.authority.key());
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveDepositorParams {
+    pub depositor: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct RemoveDepositor<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.has_role(authority.key(), Role::Admin) @ crate::errors::SolFlexError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn remove_depositor_handler(ctx: Context<RemoveDepositor>, params: RemoveDepositorParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.remove_depositor(params.depositor)?;
+
+    msg!("Depositor {} removed by: {}", params.depositor, ctx.accounts.authority.key());
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{GlobalTokenPools, TokenPool, Config};
+use crate::state::{AdminAction, Config, GlobalTokenPools, PauseScope, Proposal, TokenPool};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AddPoolParams {
@@ -14,7 +14,8 @@ pub struct AddPoolParams {
 pub struct AddPool<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
@@ -25,6 +26,10 @@ pub struct AddPool<'info> {
     )]
     pub global_pools: Account<'info, GlobalTokenPools>,
 
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -32,12 +37,18 @@ pub struct AddPool<'info> {
 }
 
 pub fn add_pool_handler(ctx: Context<AddPool>, params: AddPoolParams) -> Result<()> {
-    let config = &ctx.accounts.config;
-    let global_pools = &mut ctx.accounts.global_pools;
-    let authority = &ctx.accounts.authority;
+    ctx.accounts.config.require_not_paused(PauseScope::Pools)?;
 
     // Check authorization
-    require!(authority.key() == config.authority, crate::errors::SolFlexError::Unauthorized);
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::AddPool,
+        &[ctx.accounts.config.key(), ctx.accounts.global_pools.key()],
+        &params,
+    )?;
+
+    let global_pools = &mut ctx.accounts.global_pools;
 
     // Create new pool
     let pool = TokenPool::new(
@@ -65,7 +76,8 @@ pub struct RemovePoolParams {
 pub struct RemovePool<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
@@ -76,6 +88,10 @@ pub struct RemovePool<'info> {
     )]
     pub global_pools: Account<'info, GlobalTokenPools>,
 
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -83,12 +99,18 @@ pub struct RemovePool<'info> {
 }
 
 pub fn remove_pool_handler(ctx: Context<RemovePool>, params: RemovePoolParams) -> Result<()> {
-    let config = &ctx.accounts.config;
-    let global_pools = &mut ctx.accounts.global_pools;
-    let authority = &ctx.accounts.authority;
+    ctx.accounts.config.require_not_paused(PauseScope::Pools)?;
 
     // Check authorization
-    require!(authority.key() == config.authority, crate::errors::SolFlexError::Unauthorized);
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::RemovePool,
+        &[ctx.accounts.config.key(), ctx.accounts.global_pools.key()],
+        &params,
+    )?;
+
+    let global_pools = &mut ctx.accounts.global_pools;
 
     // Remove pool from global registry
     global_pools.remove_pool(params.pool_id)?;
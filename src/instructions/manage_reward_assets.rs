@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token::spl_token::native_mint;
+use crate::state::{Config, ConfigChange, DistributionConfig, PendingConfigChange, ReflectThrottle, RewardAsset, RewardAssetKind, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddRewardAssetParams {
+    pub min_payout: u64,
+    pub throttle: ReflectThrottle, // Budget policy for this asset's own pool
+}
+
+#[derive(Accounts)]
+#[instruction(params: AddRewardAssetParams)]
+pub struct AddRewardAsset<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.has_role(authority.key(), Role::Admin) @ crate::errors::SolFlexError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardAsset::INIT_SPACE,
+        seeds = [
+            RewardAsset::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            reward_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub reward_asset: Account<'info, RewardAsset>,
+
+    #[account(
+        mint::token_program = token_program,
+        constraint = reward_mint.key() != distribution_config.token_mint @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = reward_vault.mint == reward_mint.key() @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = reward_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_reward_asset_handler(ctx: Context<AddRewardAsset>, params: AddRewardAssetParams) -> Result<()> {
+    let distribution_config = &mut ctx.accounts.distribution_config;
+
+    params.throttle.validate()?;
+    require!(
+        distribution_config.reward_asset_count < crate::constants::MAX_REWARD_ASSETS,
+        crate::errors::SolFlexError::RewardAssetLimitReached
+    );
+
+    *ctx.accounts.reward_asset = RewardAsset::new(
+        distribution_config.key(),
+        RewardAssetKind::Token,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+        ctx.accounts.token_program.key(),
+        params.min_payout,
+        params.throttle,
+        ctx.bumps.reward_asset,
+    );
+    distribution_config.reward_asset_count += 1;
+    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Reward asset {} added to distribution {} by: {}",
+        ctx.accounts.reward_mint.key(),
+        distribution_config.token_mint,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: AddRewardAssetParams)]
+pub struct AddNativeRewardAsset<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.has_role(authority.key(), Role::Admin) @ crate::errors::SolFlexError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardAsset::INIT_SPACE,
+        seeds = [
+            RewardAsset::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            native_mint::ID.as_ref()
+        ],
+        bump
+    )]
+    pub reward_asset: Account<'info, RewardAsset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_native_reward_asset_handler(ctx: Context<AddNativeRewardAsset>, params: AddRewardAssetParams) -> Result<()> {
+    let distribution_config = &mut ctx.accounts.distribution_config;
+
+    params.throttle.validate()?;
+    require!(
+        distribution_config.reward_asset_count < crate::constants::MAX_REWARD_ASSETS,
+        crate::errors::SolFlexError::RewardAssetLimitReached
+    );
+
+    // The PDA is its own lamport vault; its rent-exempt reserve is never reflected.
+    let reward_asset_key = ctx.accounts.reward_asset.key();
+    *ctx.accounts.reward_asset = RewardAsset::new(
+        distribution_config.key(),
+        RewardAssetKind::NativeSol,
+        native_mint::ID,
+        reward_asset_key,
+        System::id(),
+        params.min_payout,
+        params.throttle,
+        ctx.bumps.reward_asset,
+    );
+    distribution_config.reward_asset_count += 1;
+    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Native SOL reward asset added to distribution {} by: {}",
+        distribution_config.token_mint,
+        ctx.accounts.authority.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveRewardAsset<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [
+            RewardAsset::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            reward_asset.reward_mint.as_ref()
+        ],
+        bump = reward_asset.bump
+    )]
+    pub reward_asset: Account<'info, RewardAsset>,
+
+    /// The queued RemoveRewardAsset change for this asset.
+    #[account(
+        mut,
+        seeds = [PendingConfigChange::SEED_PREFIX, pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        close = queued_by,
+        constraint = pending_change.target == distribution_config.key() @ crate::errors::SolFlexError::InvalidConfig,
+        constraint = pending_change.change == ConfigChange::RemoveRewardAsset(reward_asset.reward_mint) @ crate::errors::SolFlexError::InvalidConfig
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    /// Receives the rent of both closed PDAs.
+    #[account(
+        mut,
+        address = pending_change.queued_by @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub queued_by: SystemAccount<'info>,
+
+    /// The distribution's project account; its owner is the treasury that receives the sweep.
+    #[account(
+        address = distribution_config.project_account @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub project_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token assets only: the asset's vault, swept on removal.
+    #[account(
+        mut,
+        address = reward_asset.vault @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token assets only.
+    #[account(
+        address = reward_asset.reward_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token assets only: the treasury's account for `reward_mint`.
+    #[account(
+        mut,
+        constraint = sweep_destination.mint == reward_asset.reward_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = sweep_destination.owner == project_token_account.owner @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub sweep_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: NativeSol assets only: the treasury wallet, credited directly with the lamports
+    /// above the PDA's rent-exempt reserve.
+    #[account(
+        mut,
+        address = project_token_account.owner @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Any signer may carry out the removal once its delay has elapsed.
+    pub executor: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn remove_reward_asset_handler(ctx: Context<RemoveRewardAsset>) -> Result<()> {
+    let reward_asset = &ctx.accounts.reward_asset;
+
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.pending_change.executable_at,
+        crate::errors::SolFlexError::TimelockNotElapsed
+    );
+
+    // Undistributed rewards are swept rather than stranded, but only to the distribution's
+    // treasury (the owner of its timelocked project account), never to an account the
+    // caller picks.
+    let swept = match reward_asset.kind {
+        RewardAssetKind::Token => {
+            let (Some(reward_vault), Some(reward_mint), Some(sweep_destination), Some(token_program)) = (
+                ctx.accounts.reward_vault.as_ref(),
+                ctx.accounts.reward_mint.as_ref(),
+                ctx.accounts.sweep_destination.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(crate::errors::SolFlexError::InvalidTokenAccount);
+            };
+            require!(
+                token_program.key() == reward_asset.token_program,
+                crate::errors::SolFlexError::InvalidTokenAccount
+            );
+
+            let amount = reward_vault.amount;
+            if amount > 0 {
+                let distribution_config = &ctx.accounts.distribution_config;
+                let token_mint_key = distribution_config.token_mint;
+                let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: reward_vault.to_account_info(),
+                            mint: reward_mint.to_account_info(),
+                            to: sweep_destination.to_account_info(),
+                            authority: distribution_config.to_account_info(),
+                        },
+                        &[signer_seeds],
+                    ),
+                    amount,
+                    reward_mint.decimals,
+                )?;
+            }
+            amount
+        }
+        RewardAssetKind::NativeSol => {
+            let treasury = ctx
+                .accounts
+                .treasury
+                .as_ref()
+                .ok_or(crate::errors::SolFlexError::InvalidParameters)?;
+            let reward_asset_info = reward_asset.to_account_info();
+            let reserve = Rent::get()?.minimum_balance(reward_asset_info.data_len());
+            let amount = reward_asset_info.lamports().saturating_sub(reserve);
+            if amount > 0 {
+                **reward_asset_info.try_borrow_mut_lamports()? -= amount;
+                **treasury.try_borrow_mut_lamports()? += amount;
+            }
+            amount
+        }
+    };
+
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    distribution_config.reward_asset_count -= 1;
+    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Reward asset {} removed from distribution {} by: {}, swept {} to {}",
+        reward_asset.reward_mint,
+        distribution_config.token_mint,
+        ctx.accounts.executor.key(),
+        swept,
+        ctx.accounts.project_token_account.owner
+    );
+    Ok(())
+}
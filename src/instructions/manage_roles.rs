@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Config, Proposal, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    pub account: Pubkey,
+    pub role: Role,
+}
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn grant_role_handler(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::GrantRole,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+
+    config.grant_role(params.account, params.role)?;
+
+    msg!("Role {:?} granted to {} by: {}", params.role, params.account, ctx.accounts.authority.key());
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    pub account: Pubkey,
+    pub role: Role,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::RevokeRole,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+
+    config.revoke_role(params.account, params.role)?;
+
+    msg!("Role {:?} revoked from {} by: {}", params.role, params.account, ctx.accounts.authority.key());
+    Ok(())
+}
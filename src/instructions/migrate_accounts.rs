@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::{Config, LegacyConfig, LegacyUserPreferences, UserPreferences};
+
+// Grows `account` to `space` (the payer tops up rent) and writes `state` over the old layout.
+fn rewrite_account<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    state: &T,
+    space: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(space, true)?;
+
+    // Zero the whole account first so no byte of the legacy layout survives past the new data.
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    state.try_serialize(&mut &mut data[..])
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Config at the legacy layout; parsed as LegacyConfig in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The legacy authority; pays the rent for the larger account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_config_handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let legacy = LegacyConfig::try_from_account(&ctx.accounts.config)?;
+    require!(
+        legacy.authority == ctx.accounts.authority.key(),
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    let config = Config::from_legacy(legacy);
+    rewrite_account(
+        &ctx.accounts.config.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &config,
+        8 + Config::INIT_SPACE,
+    )?;
+
+    msg!("Config migrated to layout version {}", config.version);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserPreferences<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: UserPreferences at the legacy layout; parsed as LegacyUserPreferences in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [UserPreferences::SEED_PREFIX, user.key().as_ref()],
+        bump
+    )]
+    pub user_preferences: UncheckedAccount<'info>,
+
+    /// CHECK: Owner whose preference PDA is migrated.
+    pub user: UncheckedAccount<'info>,
+
+    /// Any signer may migrate an account; it pays the rent for the larger account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_preferences_handler(ctx: Context<MigrateUserPreferences>) -> Result<()> {
+    let legacy = LegacyUserPreferences::try_from_account(&ctx.accounts.user_preferences)?;
+    require!(legacy.owner == ctx.accounts.user.key(), crate::errors::SolFlexError::InvalidConfig);
+
+    // Legacy holders join the registry after every holder registered so far, so running
+    // cycles are unaffected and they are picked up from the next one.
+    let config = &mut ctx.accounts.config;
+    let holder_index = config.holder_count;
+    config.holder_count = holder_index
+        .checked_add(1)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+    rewrite_account(
+        &ctx.accounts.user_preferences.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &UserPreferences::from_legacy(legacy, holder_index),
+        8 + UserPreferences::INIT_SPACE,
+    )?;
+
+    msg!("User preferences of {} migrated as holder {}", ctx.accounts.user.key(), holder_index);
+    Ok(())
+}
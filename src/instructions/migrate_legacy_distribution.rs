@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, DistributionConfig, LegacyDistributionConfig, Role};
+use crate::token_utils::token_account_amount;
+
+#[derive(Accounts)]
+pub struct MigrateLegacyDistribution<'info> {
+    /// Must already be at the current layout; run migrate_config first.
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.has_role(authority.key(), Role::Admin) @ crate::errors::SolFlexError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: singleton distribution PDA from before per-mint seeds; parsed as LegacyDistributionConfig.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [DistributionConfig::SEED_PREFIX],
+        bump
+    )]
+    pub legacy_distribution: UncheckedAccount<'info>,
+
+    /// Per-mint distribution of the legacy mint, created beforehand with set_distribution_config.
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        constraint = legacy_fee_vault.owner == legacy_distribution.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub legacy_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<MigrateLegacyDistribution>) -> Result<()> {
+    let legacy = LegacyDistributionConfig::try_from_account(&ctx.accounts.legacy_distribution)?;
+    require!(
+        legacy.token_mint == ctx.accounts.distribution_config.token_mint,
+        crate::errors::SolFlexError::InvalidConfig
+    );
+    require!(
+        legacy.fee_vault == ctx.accounts.legacy_fee_vault.key(),
+        crate::errors::SolFlexError::InvalidTokenAccount
+    );
+
+    let legacy_info = ctx.accounts.legacy_distribution.to_account_info();
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, &[legacy.bump]];
+
+    // Move the whole legacy vault; the pools are credited with what actually arrived.
+    let amount = ctx.accounts.legacy_fee_vault.amount;
+    let fee_vault_info = ctx.accounts.fee_vault.to_account_info();
+    let vault_before = token_account_amount(&fee_vault_info)?;
+    if amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.legacy_fee_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: fee_vault_info.clone(),
+                    authority: legacy_info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    let received = token_account_amount(&fee_vault_info)?.saturating_sub(vault_before);
+
+    // The legacy dev_pool stays owed to dev; everything else, unrecorded surplus included,
+    // was reflection.
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let dev = legacy.dev_pool.min(received);
+    distribution_config.dev_pool = distribution_config
+        .dev_pool
+        .checked_add(dev)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+    distribution_config.credit_reflection(received - dev)?;
+    distribution_config.total_fees = distribution_config
+        .total_fees
+        .checked_add(legacy.total_fees)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.legacy_fee_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: legacy_info.clone(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    // Close the legacy PDA so the migration cannot run twice.
+    let authority_info = ctx.accounts.authority.to_account_info();
+    let lamports = legacy_info.lamports();
+    **authority_info.try_borrow_mut_lamports()? = authority_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+    **legacy_info.try_borrow_mut_lamports()? = 0;
+    legacy_info.assign(&System::id());
+    legacy_info.realloc(0, false)?;
+
+    msg!(
+        "Migrated legacy distribution for {}: moved {}, reflection={}, dev={}",
+        legacy.token_mint,
+        received,
+        received - dev,
+        dev
+    );
+    Ok(())
+}
@@ -9,15 +9,175 @@ pub mod ban_user;
 pub mod manage_pool;
 pub mod set_distribution_config;
 pub mod record_fees;
+pub mod claim;
+pub mod stake;
+pub mod publish_distribution_root;
+pub mod claim_reflection;
+pub mod reclaim_epoch;
+pub mod withdraw_project;
+pub mod harvest_withheld_fees;
+pub mod manage_depositors;
+pub mod deposit_fees;
+pub mod reconcile;
+pub mod manage_reward_assets;
+pub mod deposit_reward;
+pub mod deposit_sol_reward;
+pub mod claim_pending;
+pub mod transfer_authority;
+pub mod multisig;
+pub mod manage_roles;
+pub mod timelock;
+pub mod pause;
+pub mod migrate_legacy_distribution;
+pub mod migrate_accounts;
 
-pub use initialize::*;
-pub use initialize_global_pools::*;
-pub use update_config::*;
-pub use add_to_blocklist::*;
-pub use remove_from_blocklist::*;
-pub use reflect::*;
-pub use set_user_preferences::*;
-pub use ban_user::*;
-pub use manage_pool::*;
-pub use set_distribution_config::*;
-pub use record_fees::*;
\ No newline at end of file
+pub use initialize::{Initialize};
+pub use initialize_global_pools::{InitializeGlobalPools};
+pub use update_config::{UpdateConfigParams, UpdateConfig};
+pub use add_to_blocklist::{AddToBlocklistParams, AddToBlocklist};
+pub use remove_from_blocklist::{RemoveFromBlocklistParams, RemoveFromBlocklist};
+pub use reflect::{REWARD_ASSET_ACCOUNTS, SwapRoute, ReflectParams, Reflect};
+pub use set_user_preferences::{SetUserPreferencesParams, SetUserPreferences};
+pub use ban_user::{BanUserParams, BanUser};
+pub use manage_pool::{AddPoolParams, AddPool, RemovePoolParams, RemovePool};
+pub use set_distribution_config::{SetDistributionConfigParams, SetDistributionConfig};
+pub use record_fees::{RecordFeesParams, RecordFees};
+pub use claim::{Claim};
+pub use stake::{StakeParams, Stake, UnstakeParams, Unstake};
+pub use publish_distribution_root::{PublishDistributionRootParams, PublishDistributionRoot};
+pub use claim_reflection::{ClaimReflectionParams, ClaimReflection};
+pub use reclaim_epoch::{ReclaimEpochParams, ReclaimEpoch};
+pub use withdraw_project::{WithdrawProject};
+pub use harvest_withheld_fees::{HarvestWithheldFees};
+pub use manage_depositors::{AddDepositorParams, AddDepositor, RemoveDepositorParams, RemoveDepositor};
+pub use deposit_fees::{DepositFeesParams, DepositFees};
+pub use reconcile::{ReconcileParams, Reconcile};
+pub use manage_reward_assets::{AddRewardAssetParams, AddRewardAsset, AddNativeRewardAsset, RemoveRewardAsset};
+pub use deposit_reward::{DepositRewardParams, DepositReward};
+pub use deposit_sol_reward::{DepositSolRewardParams, DepositSolReward};
+pub use claim_pending::{ClaimPending};
+pub use transfer_authority::{ProposeAuthorityParams, ProposeAuthority, AcceptAuthority, CancelAuthorityTransfer};
+pub use multisig::{
+    SetMultisigParams,
+    SetMultisig,
+    CreateProposalParams,
+    CreateProposal,
+    ApproveProposal,
+    CloseProposal,
+};
+pub use manage_roles::{GrantRoleParams, GrantRole, RevokeRoleParams, RevokeRole};
+pub use timelock::{QueueConfigChangeParams, QueueConfigChange, ExecuteConfigChange, CancelConfigChange};
+pub use pause::{SetPauseParams, SetPause, SetGuardianParams, SetGuardian};
+pub use migrate_legacy_distribution::{MigrateLegacyDistribution};
+pub use migrate_accounts::{MigrateConfig, MigrateUserPreferences};
+
+// Account modules generated by #[derive(Accounts)]; #[program] expects them at the crate root.
+pub(crate) use initialize::{__client_accounts_initialize};
+pub(crate) use initialize_global_pools::{__client_accounts_initialize_global_pools};
+pub(crate) use update_config::{__client_accounts_update_config};
+pub(crate) use add_to_blocklist::{__client_accounts_add_to_blocklist};
+pub(crate) use remove_from_blocklist::{__client_accounts_remove_from_blocklist};
+pub(crate) use reflect::{__client_accounts_reflect};
+pub(crate) use set_user_preferences::{__client_accounts_set_user_preferences};
+pub(crate) use ban_user::{__client_accounts_ban_user};
+pub(crate) use manage_pool::{__client_accounts_add_pool, __client_accounts_remove_pool};
+pub(crate) use set_distribution_config::{__client_accounts_set_distribution_config};
+pub(crate) use record_fees::{__client_accounts_record_fees};
+pub(crate) use claim::{__client_accounts_claim};
+pub(crate) use stake::{__client_accounts_stake, __client_accounts_unstake};
+pub(crate) use publish_distribution_root::{__client_accounts_publish_distribution_root};
+pub(crate) use claim_reflection::{__client_accounts_claim_reflection};
+pub(crate) use reclaim_epoch::{__client_accounts_reclaim_epoch};
+pub(crate) use withdraw_project::{__client_accounts_withdraw_project};
+pub(crate) use harvest_withheld_fees::{__client_accounts_harvest_withheld_fees};
+pub(crate) use manage_depositors::{__client_accounts_add_depositor, __client_accounts_remove_depositor};
+pub(crate) use deposit_fees::{__client_accounts_deposit_fees};
+pub(crate) use reconcile::{__client_accounts_reconcile};
+pub(crate) use manage_reward_assets::{
+    __client_accounts_add_reward_asset,
+    __client_accounts_add_native_reward_asset,
+    __client_accounts_remove_reward_asset,
+};
+pub(crate) use deposit_reward::{__client_accounts_deposit_reward};
+pub(crate) use deposit_sol_reward::{__client_accounts_deposit_sol_reward};
+pub(crate) use claim_pending::{__client_accounts_claim_pending};
+pub(crate) use transfer_authority::{
+    __client_accounts_propose_authority,
+    __client_accounts_accept_authority,
+    __client_accounts_cancel_authority_transfer,
+};
+pub(crate) use multisig::{
+    __client_accounts_set_multisig,
+    __client_accounts_create_proposal,
+    __client_accounts_approve_proposal,
+    __client_accounts_close_proposal,
+};
+pub(crate) use manage_roles::{__client_accounts_grant_role, __client_accounts_revoke_role};
+pub(crate) use timelock::{
+    __client_accounts_queue_config_change,
+    __client_accounts_execute_config_change,
+    __client_accounts_cancel_config_change,
+};
+pub(crate) use pause::{__client_accounts_set_pause, __client_accounts_set_guardian};
+pub(crate) use migrate_legacy_distribution::{__client_accounts_migrate_legacy_distribution};
+pub(crate) use migrate_accounts::{__client_accounts_migrate_config, __client_accounts_migrate_user_preferences};
+
+#[cfg(feature = "cpi")]
+mod cpi_accounts {
+    use super::*;
+
+    pub(crate) use initialize::{__cpi_client_accounts_initialize};
+    pub(crate) use initialize_global_pools::{__cpi_client_accounts_initialize_global_pools};
+    pub(crate) use update_config::{__cpi_client_accounts_update_config};
+    pub(crate) use add_to_blocklist::{__cpi_client_accounts_add_to_blocklist};
+    pub(crate) use remove_from_blocklist::{__cpi_client_accounts_remove_from_blocklist};
+    pub(crate) use reflect::{__cpi_client_accounts_reflect};
+    pub(crate) use set_user_preferences::{__cpi_client_accounts_set_user_preferences};
+    pub(crate) use ban_user::{__cpi_client_accounts_ban_user};
+    pub(crate) use manage_pool::{__cpi_client_accounts_add_pool, __cpi_client_accounts_remove_pool};
+    pub(crate) use set_distribution_config::{__cpi_client_accounts_set_distribution_config};
+    pub(crate) use record_fees::{__cpi_client_accounts_record_fees};
+    pub(crate) use claim::{__cpi_client_accounts_claim};
+    pub(crate) use stake::{__cpi_client_accounts_stake, __cpi_client_accounts_unstake};
+    pub(crate) use publish_distribution_root::{__cpi_client_accounts_publish_distribution_root};
+    pub(crate) use claim_reflection::{__cpi_client_accounts_claim_reflection};
+    pub(crate) use reclaim_epoch::{__cpi_client_accounts_reclaim_epoch};
+    pub(crate) use withdraw_project::{__cpi_client_accounts_withdraw_project};
+    pub(crate) use harvest_withheld_fees::{__cpi_client_accounts_harvest_withheld_fees};
+    pub(crate) use manage_depositors::{__cpi_client_accounts_add_depositor, __cpi_client_accounts_remove_depositor};
+    pub(crate) use deposit_fees::{__cpi_client_accounts_deposit_fees};
+    pub(crate) use reconcile::{__cpi_client_accounts_reconcile};
+    pub(crate) use manage_reward_assets::{
+        __cpi_client_accounts_add_reward_asset,
+        __cpi_client_accounts_add_native_reward_asset,
+        __cpi_client_accounts_remove_reward_asset,
+    };
+    pub(crate) use deposit_reward::{__cpi_client_accounts_deposit_reward};
+    pub(crate) use deposit_sol_reward::{__cpi_client_accounts_deposit_sol_reward};
+    pub(crate) use claim_pending::{__cpi_client_accounts_claim_pending};
+    pub(crate) use transfer_authority::{
+        __cpi_client_accounts_propose_authority,
+        __cpi_client_accounts_accept_authority,
+        __cpi_client_accounts_cancel_authority_transfer,
+    };
+    pub(crate) use multisig::{
+        __cpi_client_accounts_set_multisig,
+        __cpi_client_accounts_create_proposal,
+        __cpi_client_accounts_approve_proposal,
+        __cpi_client_accounts_close_proposal,
+    };
+    pub(crate) use manage_roles::{__cpi_client_accounts_grant_role, __cpi_client_accounts_revoke_role};
+    pub(crate) use timelock::{
+        __cpi_client_accounts_queue_config_change,
+        __cpi_client_accounts_execute_config_change,
+        __cpi_client_accounts_cancel_config_change,
+    };
+    pub(crate) use pause::{__cpi_client_accounts_set_pause, __cpi_client_accounts_set_guardian};
+    pub(crate) use migrate_legacy_distribution::{__cpi_client_accounts_migrate_legacy_distribution};
+    pub(crate) use migrate_accounts::{
+        __cpi_client_accounts_migrate_config,
+        __cpi_client_accounts_migrate_user_preferences,
+    };
+}
+#[cfg(feature = "cpi")]
+pub(crate) use cpi_accounts::*;
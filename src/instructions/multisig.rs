@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Config, Proposal};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMultisigParams {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved SetMultisig proposal; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_multisig_handler(ctx: Context<SetMultisig>, params: SetMultisigParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::SetMultisig,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.set_multisig(params.signers, params.threshold)?;
+
+    msg!("Multisig set to {} of {} signers", config.threshold, config.signers.len());
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateProposalParams {
+    pub action: AdminAction,
+    pub params_hash: [u8; 32], // Proposal::hash_params of the target accounts and params the action will be executed with
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.is_multisig_signer(proposer.key()) @ crate::errors::SolFlexError::NotMultisigSigner
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [Proposal::SEED_PREFIX, config.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal_handler(ctx: Context<CreateProposal>, params: CreateProposalParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let id = config.proposal_count;
+
+    // The proposer's approval is recorded on creation.
+    *ctx.accounts.proposal = Proposal::new(
+        id,
+        ctx.accounts.proposer.key(),
+        params.action,
+        params.params_hash,
+        config.multisig_nonce,
+        ctx.bumps.proposal,
+    );
+    config.proposal_count = id
+        .checked_add(1)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+    msg!("Proposal {} created for {:?} by: {}", id, params.action, ctx.accounts.proposer.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated,
+        constraint = config.is_multisig_signer(signer.key()) @ crate::errors::SolFlexError::NotMultisigSigner
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::SEED_PREFIX, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub signer: Signer<'info>,
+}
+
+pub fn approve_proposal_handler(ctx: Context<ApproveProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    proposal.approve(&ctx.accounts.config, ctx.accounts.signer.key())?;
+
+    msg!(
+        "Proposal {} approved by {} ({}/{})",
+        proposal.id,
+        ctx.accounts.signer.key(),
+        proposal.approval_count(&ctx.accounts.config),
+        ctx.accounts.config.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::SEED_PREFIX, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Receives the proposal rent back.
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+pub fn close_proposal_handler(ctx: Context<CloseProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    // Executed, expired and stale proposals can no longer be used.
+    require!(proposal.is_closed(&ctx.accounts.config), crate::errors::SolFlexError::ProposalStillOpen);
+
+    msg!("Proposal {} closed by: {}", proposal.id, ctx.accounts.proposer.key());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Config, PauseFlags, Proposal};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPauseParams {
+    pub flags: PauseFlags, // Replaces the current flags
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured
+    /// unless the guardian is only adding flags.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    /// Config authority, or the guardian when only adding flags.
+    pub authority: Signer<'info>,
+}
+
+pub fn set_pause_handler(ctx: Context<SetPause>, params: SetPauseParams) -> Result<()> {
+    let signer = ctx.accounts.authority.key();
+    if !ctx.accounts.config.is_guardian_pause(signer, &params.flags) {
+        ctx.accounts.config.authorize_admin(
+            signer,
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetPause,
+            &[ctx.accounts.config.key()],
+            &params,
+        )?;
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.set_pause(params.flags)?;
+
+    msg!("Pause flags set to {:?} by: {}", params.flags, ctx.accounts.authority.key());
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetGuardianParams {
+    pub guardian: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_guardian_handler(ctx: Context<SetGuardian>, params: SetGuardianParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::SetGuardian,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+
+    config.guardian = params.guardian;
+    config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("Guardian set to {:?} by: {}", params.guardian, ctx.accounts.authority.key());
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, DistributionConfig, DistributionEpoch, PauseScope, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PublishDistributionRootParams {
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub num_leaves: u32,
+    pub claim_deadline: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PublishDistributionRootParams)]
+pub struct PublishDistributionRoot<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DistributionEpoch::INIT_SPACE,
+        seeds = [
+            DistributionEpoch::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            (distribution_config.current_epoch + 1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub distribution_epoch: Account<'info, DistributionEpoch>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PublishDistributionRoot>, params: PublishDistributionRootParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let distribution_epoch = &mut ctx.accounts.distribution_epoch;
+
+    require!(
+        config.has_role(ctx.accounts.authority.key(), Role::Cranker),
+        crate::errors::SolFlexError::Unauthorized
+    );
+    require!(params.total_amount > 0, crate::errors::SolFlexError::InvalidParameters);
+    require!(
+        params.num_leaves > 0 && params.num_leaves <= crate::constants::MAX_EPOCH_LEAVES,
+        crate::errors::SolFlexError::InvalidParameters
+    );
+
+    require!(
+        params.claim_deadline > Clock::get()?.unix_timestamp,
+        crate::errors::SolFlexError::InvalidParameters
+    );
+
+    // Reserve the epoch total out of the reflection pool so push batches cannot spend it.
+    distribution_config.distribute_reflection(params.total_amount)?;
+    distribution_config.claims_pool = distribution_config
+        .claims_pool
+        .checked_add(params.total_amount)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+    let epoch = distribution_config.current_epoch + 1;
+    distribution_config.current_epoch = epoch;
+
+    **distribution_epoch = DistributionEpoch::new(
+        epoch,
+        params.merkle_root,
+        params.total_amount,
+        params.num_leaves,
+        params.claim_deadline,
+        ctx.bumps.distribution_epoch,
+    );
+
+    msg!(
+        "Published distribution epoch {}: total={}, leaves={}, deadline={}",
+        epoch,
+        params.total_amount,
+        params.num_leaves,
+        params.claim_deadline
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, DistributionConfig, DistributionEpoch, PauseScope, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReclaimEpochParams {
+    pub epoch: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: ReclaimEpochParams)]
+pub struct ReclaimEpoch<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [DistributionEpoch::SEED_PREFIX, distribution_config.key().as_ref(), params.epoch.to_le_bytes().as_ref()],
+        bump = distribution_epoch.bump
+    )]
+    pub distribution_epoch: Account<'info, DistributionEpoch>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReclaimEpoch>, params: ReclaimEpochParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let distribution_epoch = &ctx.accounts.distribution_epoch;
+
+    require!(
+        config.has_role(ctx.accounts.authority.key(), Role::Cranker),
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    // Unclaimed leaves go back to the reflection pool; the epoch account is closed.
+    let unclaimed = distribution_epoch.unclaimed_amount(Clock::get()?.unix_timestamp)?;
+    distribution_config.claims_pool = distribution_config
+        .claims_pool
+        .checked_sub(unclaimed)
+        .ok_or(crate::errors::SolFlexError::InsufficientFunds)?;
+    distribution_config.credit_reflection(unclaimed)?;
+
+    msg!("Reclaimed {} unclaimed from epoch {}", unclaimed, params.epoch);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{Config, DistributionConfig, Role};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReconcileParams {
+    pub credit_surplus: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(params: ReconcileParams)]
+pub struct Reconcile<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        constraint = fee_vault.key() == distribution_config.fee_vault @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Reconcile>, params: ReconcileParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let is_treasurer = config.has_role(ctx.accounts.authority.key(), Role::Treasurer);
+
+    // Anyone may detect a deficit; crediting surplus and lifting a halt need the Treasurer role.
+    require!(
+        is_treasurer || !params.credit_surplus,
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    let vault_amount = ctx.accounts.fee_vault.amount;
+    let tracked = distribution_config.tracked_total()?;
+
+    if vault_amount < tracked {
+        distribution_config.halted = true;
+        distribution_config.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Vault deficit: vault={}, tracked={}, deficit={}; distribution halted",
+            vault_amount,
+            tracked,
+            tracked - vault_amount
+        );
+        return Ok(());
+    }
+
+    let surplus = vault_amount - tracked;
+    if surplus > 0 && params.credit_surplus {
+        distribution_config.credit_reflection(surplus)?;
+    }
+    if distribution_config.halted && is_treasurer {
+        distribution_config.halted = false;
+        msg!("Vault covers tracked pools again; distribution resumed");
+    }
+    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Vault reconciled: vault={}, tracked={}, surplus={}, credited={}",
+        vault_amount,
+        tracked,
+        surplus,
+        surplus > 0 && params.credit_surplus
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount as SplTokenAccount};
-use crate::state::{Config, DistributionConfig};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+use crate::state::{Config, DistributionConfig, PauseScope, Role};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RecordFeesParams {
@@ -12,13 +12,14 @@ pub struct RecordFeesParams {
 pub struct RecordFees<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
     #[account(
         mut,
-        seeds = [DistributionConfig::SEED_PREFIX],
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
         bump = distribution_config.bump
     )]
     pub distribution_config: Account<'info, DistributionConfig>,
@@ -29,58 +30,75 @@ pub struct RecordFees<'info> {
         constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
         constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
     )]
-    pub fee_vault: Account<'info, SplTokenAccount>,
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<RecordFees>, params: RecordFeesParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Fees)?;
+
     let config = &ctx.accounts.config;
     let distribution_config = &mut ctx.accounts.distribution_config;
 
     require!(
-        ctx.accounts.authority.key() == config.authority,
+        config.has_role(ctx.accounts.authority.key(), Role::FeeRecorder),
         crate::errors::SolFlexError::Unauthorized
     );
     require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
 
-    // For the current distribution model, incoming fees are split only
-    // between reflection and dev pools.
-    distribution_config.validate_reflection_dev_only_split()?;
-
-    let reflection_amount = params
-        .amount
-        .checked_mul(distribution_config.reflection_rate as u64)
-        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
-        / 10000;
-    let dev_amount = params
-        .amount
-        .checked_mul(distribution_config.dev_fee_rate as u64)
-        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
-        / 10000;
+    // Incoming fees are split between reflection, burn, project and dev pools.
+    let split = distribution_config.record_fees(params.amount)?;
 
-    distribution_config.reflection_pool = distribution_config
-        .reflection_pool
-        .checked_add(reflection_amount)
-        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
-    distribution_config.dev_pool = distribution_config
-        .dev_pool
-        .checked_add(dev_amount)
-        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
-    distribution_config.total_fees = distribution_config
-        .total_fees
-        .checked_add(params.amount)
-        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
-    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+    burn_pending(
+        distribution_config,
+        ctx.accounts.fee_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
 
     msg!(
-        "Recorded {} incoming fees => reflection={}, dev={}",
+        "Recorded {} incoming fees => reflection={}, burn={}, project={}, dev={}, total burned={}",
         params.amount,
-        reflection_amount,
-        dev_amount
+        split.reflection,
+        split.burn,
+        split.project,
+        split.dev,
+        distribution_config.total_burned
     );
     Ok(())
 }
+
+/// Burns the whole burn bucket out of fee_vault, signed by the distribution_config PDA.
+pub fn burn_pending<'info>(
+    distribution_config: &mut Account<'info, DistributionConfig>,
+    fee_vault: AccountInfo<'info>,
+    token_mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    if distribution_config.burn_pool == 0 {
+        return Ok(());
+    }
+    let burn_total = distribution_config.burn_pool;
+    let token_mint_key = distribution_config.token_mint;
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+    let cpi_accounts = Burn {
+        mint: token_mint,
+        from: fee_vault,
+        authority: distribution_config.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new_with_signer(token_program, cpi_accounts, &[signer_seeds]),
+        burn_total,
+    )?;
+    distribution_config.record_burn(burn_total)
+}
@@ -1,17 +1,140 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use crate::state::{Config, DistributionConfig, GlobalTokenPools, UserPreferences};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, CyclePhase, DistributionConfig, DistributionMode, GlobalTokenPools, PauseScope, RewardAsset, RewardAssetKind, Role, UserPreferences};
+use crate::swap_adapter::{SwapRouteAccounts, SWAP_ROUTE_ACCOUNTS};
+use crate::token_utils::{associated_token_account_len, load_token_account, net_transfer_amount, token_account_amount};
+
+// Accounts supplied per basket reward asset in `reflect` remaining accounts:
+// [reward_asset, reward_vault, reward_mint, reward_token_program]
+// (NativeSol: [reward_asset, reward_asset, native_mint, system_program])
+pub const REWARD_ASSET_ACCOUNTS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapRoute {
+    pub pool_id: u64,
+    pub min_out_rate: u64, // Minimum pool token out per base token in, scaled by SWAP_RATE_PRECISION
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReflectParams {
+    pub pair_count: u16,
+    pub swap_routes: Vec<SwapRoute>,
+}
+
+#[derive(PartialEq)]
+enum Delivery {
+    Pay, // Transfer (or swap) into the recipient's account
+    Hold, // Account cannot receive; credit the share to UserPreferences.pending_reflections
+    Skip, // Account cannot receive and no pending entry fits; the share stays in the pool
+}
+
+enum RewardDestination<'info> {
+    Receivable(AccountInfo<'info>),
+    Missing(AccountInfo<'info>), // Created by the rent reserve only if a payout is due
+    Unavailable, // Shares stay in the asset's pool
+}
+
+struct Recipient<'info> {
+    owner: Pubkey,
+    preferences: &'info AccountInfo<'info>,
+    token_account: AccountInfo<'info>, // Base asset account, used for eligibility and weight
+    balance: u64,
+    delivery: Delivery,
+    create_base: bool, // Base account is missing and may be created if a payout is due
+    rent_funded: bool, // The rent reserve created an account for this recipient in this call
+    swap: Option<(usize, AccountInfo<'info>)>, // Route index and pool token destination
+    reward_accounts: Vec<RewardDestination<'info>>, // Per basket asset, in basket order
+}
+
+struct BasketAsset<'info> {
+    asset: Account<'info, RewardAsset>,
+    vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    decimals: u8,
+}
+
+// Creates missing recipient ATAs, paid by the distribution's rent reserve up to the batch cap.
+struct AtaCreator<'a, 'info> {
+    rent: Rent,
+    distribution: Pubkey,
+    bump: u8,
+    rent_reserve: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    associated_token_program: AccountInfo<'info>,
+    wallets: &'a [AccountInfo<'info>], // Owner wallets are looked up among the trailing accounts
+    cap: u64,
+    spent: u64,
+}
+
+impl<'info> AtaCreator<'_, 'info> {
+    fn create(
+        &mut self,
+        ata: &AccountInfo<'info>,
+        owner: &Pubkey,
+        mint: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+    ) -> Result<bool> {
+        // Anything other than the owner's ATA, or an ATA without its wallet supplied, is left
+        // missing and handled like any other account that cannot receive.
+        if ata.key() != get_associated_token_address_with_program_id(owner, &mint.key(), &token_program.key()) {
+            return Ok(false);
+        }
+        let Some(wallet) = self.wallets.iter().find(|info| info.key() == *owner) else {
+            return Ok(false);
+        };
+        // The cap is checked before the CPI. Once it is reached (or the reserve cannot pay
+        // and stay rent-exempt), the account is left missing rather than failing the batch.
+        let account_rent = self.rent.minimum_balance(associated_token_account_len(mint)?);
+        let within_cap = self.spent.checked_add(account_rent).is_some_and(|spent| spent <= self.cap);
+        let reserve_required = account_rent.saturating_add(self.rent.minimum_balance(0));
+        if !within_cap || self.rent_reserve.lamports() < reserve_required {
+            msg!("Rent budget reached; not creating token account {} for {}", ata.key(), owner);
+            return Ok(false);
+        }
+
+        let reserve_seeds: &[&[u8]] = &[crate::constants::RENT_RESERVE_SEED, self.distribution.as_ref(), &[self.bump]];
+        let reserve_before = self.rent_reserve.lamports();
+        associated_token::create_idempotent(CpiContext::new_with_signer(
+            self.associated_token_program.clone(),
+            Create {
+                payer: self.rent_reserve.clone(),
+                associated_token: ata.clone(),
+                authority: wallet.clone(),
+                mint: mint.clone(),
+                system_program: self.system_program.clone(),
+                token_program: token_program.clone(),
+            },
+            &[reserve_seeds],
+        ))?;
+        self.spent += reserve_before.saturating_sub(self.rent_reserve.lamports());
+
+        msg!("Created token account {} for {}", ata.key(), owner);
+        Ok(true)
+    }
+}
+
+// A transfer into anything else would fail the whole batch: the account must belong to the
+// token program the transfer goes through, hold `mint` for `owner`, and not be frozen.
+fn is_receivable_token_account(info: &AccountInfo, token_program: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> bool {
+    info.owner == token_program
+        && load_token_account(info).is_ok_and(|token| token.mint == *mint && token.owner == *owner && !token.is_frozen())
+}
 
 #[derive(Accounts)]
+#[instruction(params: ReflectParams)]
 pub struct Reflect<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
     #[account(
-        seeds = [DistributionConfig::SEED_PREFIX],
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
         bump = distribution_config.bump
     )]
     pub distribution_config: Account<'info, DistributionConfig>,
@@ -22,19 +145,19 @@ pub struct Reflect<'info> {
         constraint = fee_vault.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
         constraint = fee_vault.owner == distribution_config.key() @ crate::errors::SolFlexError::InvalidTokenAccount
     )]
-    pub fee_vault: Account<'info, TokenAccount>,
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         constraint = dev_token_account.key() == distribution_config.dev_account @ crate::errors::SolFlexError::InvalidTokenAccount,
         constraint = dev_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
     )]
-    pub dev_token_account: Account<'info, TokenAccount>,
+    pub dev_token_account: InterfaceAccount<'info, TokenAccount>,
 
 
     #[account(
         constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -43,168 +166,790 @@ pub struct Reflect<'info> {
     )]
     pub global_pools: Account<'info, GlobalTokenPools>,
 
-    /// CHECK: Authority account
+    /// Holder of the Cranker role, or any signer when the permissionless crank is enabled.
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Receives the crank reward when a signer without the Cranker role cranks.
+    #[account(
+        mut,
+        constraint = crank_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = crank_token_account.owner == authority.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub crank_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// System-owned PDA that pays for recipient ATAs when `auto_create_accounts` is enabled.
+    #[account(
+        mut,
+        seeds = [crate::constants::RENT_RESERVE_SEED, distribution_config.key().as_ref()],
+        bump
+    )]
+    pub rent_reserve: Option<SystemAccount<'info>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Reflect>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Reflect<'info>>, params: ReflectParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
     let config = &ctx.accounts.config;
     let distribution_config = &mut ctx.accounts.distribution_config;
     let global_pools = &ctx.accounts.global_pools;
     let fee_vault = &ctx.accounts.fee_vault;
     let dev_token_account = &ctx.accounts.dev_token_account;
 
+    let is_cranker = config.has_role(ctx.accounts.authority.key(), Role::Cranker);
     require!(
-        ctx.accounts.authority.key() == config.authority,
+        is_cranker || (distribution_config.permissionless_crank && distribution_config.permits_permissionless_crank()),
         crate::errors::SolFlexError::Unauthorized
     );
 
-    // Check if there are sufficient reflections to distribute
+    require!(!distribution_config.halted, crate::errors::SolFlexError::DistributionHalted);
+
+    // Accumulator distributions are pulled by holders through `claim`.
     require!(
-        distribution_config.reflection_pool >= config.min_reflection_pool,
-        crate::errors::SolFlexError::NoReflectionsToDistribute
+        distribution_config.distribution_mode != DistributionMode::Accumulator,
+        crate::errors::SolFlexError::InvalidConfig
     );
 
+    // An empty batch resets a running cycle. Permissionless cranks can only reset once the
+    // cursor has been idle for CRANK_RESET_DELAY, so an empty batch cannot be used to cut a
+    // cycle short.
+    let now = Clock::get()?.unix_timestamp;
+    if params.pair_count == 0 {
+        require!(
+            distribution_config.cycle_phase != CyclePhase::Idle
+                && (is_cranker
+                    || now.saturating_sub(distribution_config.last_reflect_at) >= crate::constants::CRANK_RESET_DELAY),
+            crate::errors::SolFlexError::NoEligibleAccounts
+        );
+        distribution_config.end_cycle();
+        distribution_config.last_reflect_at = now;
+        distribution_config.updated_at = now;
+        msg!(
+            "Cycle {} reset after {} of {} distributed",
+            distribution_config.cycle_id,
+            distribution_config.cycle_distributed,
+            distribution_config.cycle_budget
+        );
+        return Ok(());
+    }
+
+    // A cycle is two sweeps over the holder registry in holder_index order: the snapshot
+    // sweep records each holder's balance, then the payout sweep pays against it. Check if
+    // there are sufficient reflections to open one. A distribution with basket assets (such
+    // as native SOL income) also cycles while its base pool is below the minimum; the base
+    // asset then sits the cycle out.
+    if distribution_config.cycle_phase == CyclePhase::Idle {
+        let rolled = distribution_config.roll_dust()?;
+        if rolled > 0 {
+            msg!("Rolled {} dust into the reflection pool", rolled);
+        }
+        require!(
+            distribution_config.reflection_pool >= config.min_reflection_pool || distribution_config.reward_asset_count > 0,
+            crate::errors::SolFlexError::NoReflectionsToDistribute
+        );
+        distribution_config.open_cycle(config.holder_count)?;
+        msg!(
+            "Opened cycle {} over {} holders",
+            distribution_config.cycle_id,
+            distribution_config.cycle_holders
+        );
+    }
+
     msg!(
-        "Reflection distribution: pool minimum={}, per-account minimum={}, batch limit={}",
+        "Reflection distribution: pool minimum={}, per-account minimum={}, batch limit={}, mode={:?}, throttle={:?}",
         config.min_reflection_pool,
         config.min_reflection_per_account,
-        distribution_config.limit
+        distribution_config.limit,
+        distribution_config.distribution_mode,
+        distribution_config.throttle
     );
 
     // Remaining accounts layout:
-    // [user_preferences, recipient_token_account, user_preferences, recipient_token_account, ...]
+    // [user_preferences, recipient_token_account] * pair_count,
+    // [pool, pool_input_vault, pool_output_vault, adapter_program, output_token_program, output_mint, intermediate] * swap_routes,
+    // [reward_asset, reward_vault, reward_mint, reward_token_program] * reward_asset_count,
+    // [recipient_reward_token_account * reward_asset_count] * pair_count,
+    // followed by pool token destination accounts for recipients being swapped.
+    let pair_accounts_len = params.pair_count as usize * 2;
+    if distribution_config.cycle_phase == CyclePhase::Snapshot {
+        require!(
+            ctx.remaining_accounts.len() >= pair_accounts_len,
+            crate::errors::SolFlexError::InvalidRemainingAccounts
+        );
+        return snapshot_batch(
+            config,
+            distribution_config,
+            &ctx.accounts.token_mint,
+            &ctx.remaining_accounts[..pair_accounts_len],
+            now,
+        );
+    }
+    let route_accounts_len = params.swap_routes.len() * SWAP_ROUTE_ACCOUNTS;
+    let basket_len = distribution_config.reward_asset_count as usize;
+    let basket_accounts_len = basket_len * REWARD_ASSET_ACCOUNTS;
+    let reward_accounts_len = params.pair_count as usize * basket_len;
     require!(
-        ctx.remaining_accounts.len() % 2 == 0,
+        ctx.remaining_accounts.len() >= pair_accounts_len + route_accounts_len + basket_accounts_len + reward_accounts_len,
         crate::errors::SolFlexError::InvalidRemainingAccounts
     );
+    let (pair_accounts, rest) = ctx.remaining_accounts.split_at(pair_accounts_len);
+    let (route_accounts, rest) = rest.split_at(route_accounts_len);
+    let (basket_accounts, rest) = rest.split_at(basket_accounts_len);
+    let (reward_accounts, destination_accounts) = rest.split_at(reward_accounts_len);
+
+    // Every registered basket asset must be supplied, in strictly increasing mint order.
+    let mut basket: Vec<BasketAsset<'info>> = Vec::with_capacity(basket_len);
+    for accounts in basket_accounts.chunks_exact(REWARD_ASSET_ACCOUNTS) {
+        let asset: Account<'info, RewardAsset> = Account::try_from(&accounts[0])?;
+        require!(
+            asset.distribution == distribution_config.key()
+                && accounts[1].key() == asset.vault
+                && accounts[2].key() == asset.reward_mint
+                && accounts[3].key() == asset.token_program,
+            crate::errors::SolFlexError::InvalidRemainingAccounts
+        );
+        if let Some(previous) = basket.last() {
+            require!(
+                asset.reward_mint > previous.asset.reward_mint,
+                crate::errors::SolFlexError::InvalidRemainingAccounts
+            );
+        }
+        let decimals = match asset.kind {
+            RewardAssetKind::Token => InterfaceAccount::<Mint>::try_from(&accounts[2])?.decimals,
+            RewardAssetKind::NativeSol => 9,
+        };
+        basket.push(BasketAsset {
+            asset,
+            vault: accounts[1].clone(),
+            mint: accounts[2].clone(),
+            token_program: accounts[3].clone(),
+            decimals,
+        });
+    }
+
+    // Only Cranker-role runs swap, since the caller supplies the minimum-out quotes.
+    // While pools are paused every recipient is paid in the base asset.
+    let mut routes: Vec<(u64, u64, SwapRouteAccounts<'info>, Pubkey)> = Vec::new();
+    if is_cranker && !config.pause_flags.is_paused(PauseScope::Pools) {
+        for (route, accounts) in params.swap_routes.iter().zip(route_accounts.chunks_exact(SWAP_ROUTE_ACCOUNTS)) {
+            require!(
+                !routes.iter().any(|(pool_id, ..)| *pool_id == route.pool_id),
+                crate::errors::SolFlexError::InvalidSwapRoute
+            );
+            let token_pool = global_pools.get_pool(route.pool_id)?;
+            require!(token_pool.is_active, crate::errors::SolFlexError::InvalidSwapRoute);
+            let route_accounts = SwapRouteAccounts::load(accounts, token_pool, &distribution_config.key())?;
+            routes.push((route.pool_id, route.min_out_rate, route_accounts, token_pool.token_mint));
+        }
+    }
+
+    // Opt-in: missing recipient ATAs are created instead of failing the batch.
+    let rent = Rent::get()?;
+    let mut ata_creator = match (
+        distribution_config.auto_create_accounts,
+        &ctx.accounts.rent_reserve,
+        &ctx.accounts.associated_token_program,
+    ) {
+        (true, Some(rent_reserve), Some(associated_token_program)) => Some(AtaCreator {
+            rent,
+            distribution: distribution_config.key(),
+            bump: ctx.bumps.rent_reserve,
+            rent_reserve: rent_reserve.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            associated_token_program: associated_token_program.to_account_info(),
+            wallets: destination_accounts,
+            cap: distribution_config.max_rent_per_batch,
+            spent: 0,
+        }),
+        _ => None,
+    };
 
     let batch_limit = distribution_config.limit as usize;
-    let mut recipients: Vec<AccountInfo<'_>> = Vec::new();
-    let mut new_last_paid = distribution_config.last_paid;
-    let mut last_seen_owner: Option<Pubkey> = None;
+    let mut recipients: Vec<Recipient<'info>> = Vec::new();
 
-    for pair in ctx.remaining_accounts.chunks_exact(2) {
-        if recipients.len() >= batch_limit {
+    for (pair_index, pair) in pair_accounts.chunks_exact(2).enumerate().take(batch_limit) {
+        if distribution_config.sweep_complete() {
             break;
         }
 
         let pref_info = &pair[0];
         let recipient_token_info = &pair[1];
         let user_pref: Account<UserPreferences> = Account::try_from(pref_info)?;
+        require!(
+            UserPreferences::is_current_layout(pref_info),
+            crate::errors::SolFlexError::AccountNotMigrated
+        );
 
-        // Require strictly increasing owner order to make cursoring deterministic.
-        if let Some(prev_owner) = last_seen_owner {
-            require!(user_pref.owner > prev_owner, crate::errors::SolFlexError::InvalidParameters);
-        }
-        last_seen_owner = Some(user_pref.owner);
-
-        // Cursor-based batching.
-        if user_pref.owner <= distribution_config.last_paid {
-            continue;
-        }
+        distribution_config.advance_cursor(user_pref.holder_index)?;
         if user_pref.is_banned || config.is_blocklisted(user_pref.owner) {
             continue;
         }
+        // Only holders the snapshot sweep recorded are paid, and never on more than the
+        // recorded balance, so tokens moved to a holder later in the sweep are not paid twice.
+        let Some(snapshot_balance) = user_pref.snapshot_balance(distribution_config.key(), distribution_config.cycle_id)
+        else {
+            continue;
+        };
+
+        // Creation is deferred until a payout is known to be due, and the reserve pays for
+        // an owner's accounts in at most one call; the flag is written to the preferences.
+        let can_create = ata_creator.is_some() && pref_info.is_writable && !user_pref.rent_funded;
+        // A closed, mismatched or frozen account must not fail the batch: its share is held
+        // on the user's preferences for claim_pending. A missing or mismatched account has
+        // no trusted balance, so it weighs nothing in Proportional mode.
+        let recipient_token = InterfaceAccount::<TokenAccount>::try_from(recipient_token_info)
+            .ok()
+            .filter(|token| token.mint == distribution_config.token_mint && token.owner == user_pref.owner);
+        let balance = recipient_token.as_ref().map_or(0, |token| token.amount).min(snapshot_balance);
+        let delivery = if is_receivable_token_account(
+            recipient_token_info,
+            &ctx.accounts.token_program.key(),
+            &distribution_config.token_mint,
+            &user_pref.owner,
+        ) {
+            Delivery::Pay
+        } else if pref_info.is_writable
+            && (user_pref.pending_reflections.len() < crate::constants::MAX_PENDING_REFLECTIONS
+                || user_pref
+                    .pending_reflections
+                    .iter()
+                    .any(|entry| entry.distribution == distribution_config.key()))
+        {
+            msg!("Token account of {} cannot receive; holding its share", user_pref.owner);
+            Delivery::Hold
+        } else {
+            msg!("Token account of {} cannot receive and no pending slot is free; skipping", user_pref.owner);
+            Delivery::Skip
+        };
 
-        // Default route always sends configured base asset.
-        // If a pool preference exists but is invalid/inactive, we gracefully fall back to default.
-        if user_pref.preferred_pool_id != 0 {
+        // Recipients with an active, routed pool receive that pool's token in their ATA.
+        // If a pool preference is invalid/inactive/unrouted, we gracefully fall back to default.
+        let mut swap = None;
+        if user_pref.preferred_pool_id != 0 && delivery == Delivery::Pay {
             let pool_valid_and_active = global_pools
                 .get_pool(user_pref.preferred_pool_id)
                 .map(|p| p.is_active)
                 .unwrap_or(false);
-            if !pool_valid_and_active {
-                msg!(
-                    "Pool {} invalid/inactive for user {}, defaulting to configured asset",
+            let route_index = routes
+                .iter()
+                .position(|(pool_id, ..)| *pool_id == user_pref.preferred_pool_id);
+            match route_index {
+                Some(index) if pool_valid_and_active => {
+                    let (_, _, route, pool_mint) = &routes[index];
+                    let destination_key = get_associated_token_address_with_program_id(
+                        &user_pref.owner,
+                        pool_mint,
+                        &route.output_token_program.key(),
+                    );
+                    // An uninitialized, reassigned or frozen pool token account would fail the
+                    // pool's transfers, so the recipient is paid in the base asset instead.
+                    match destination_accounts.iter().find(|info| info.key() == destination_key) {
+                        Some(destination)
+                            if is_receivable_token_account(
+                                destination,
+                                &route.output_token_program.key(),
+                                pool_mint,
+                                &user_pref.owner,
+                            ) =>
+                        {
+                            swap = Some((index, destination.clone()))
+                        }
+                        Some(_) => msg!(
+                            "Pool {} token account of user {} cannot receive, defaulting to configured asset",
+                            user_pref.preferred_pool_id,
+                            user_pref.owner
+                        ),
+                        None => msg!(
+                            "No pool {} token account supplied for user {}, defaulting to configured asset",
+                            user_pref.preferred_pool_id,
+                            user_pref.owner
+                        ),
+                    }
+                }
+                _ => msg!(
+                    "Pool {} invalid/inactive/unrouted for user {}, defaulting to configured asset",
                     user_pref.preferred_pool_id,
                     user_pref.owner
-                );
+                ),
             }
         }
 
-        let recipient_token: Account<TokenAccount> = Account::try_from(recipient_token_info)?;
-        require!(
-            recipient_token.mint == distribution_config.token_mint
-                && recipient_token.owner == user_pref.owner,
-            crate::errors::SolFlexError::InvalidTokenAccount
-        );
+        let recipient_reward_accounts = &reward_accounts[pair_index * basket_len..(pair_index + 1) * basket_len];
+        let mut recipient_rewards = Vec::with_capacity(basket_len);
+        for (asset, reward_info) in basket.iter().zip(recipient_reward_accounts.iter()) {
+            let receivable = match asset.asset.kind {
+                RewardAssetKind::Token if can_create && reward_info.data_is_empty() => {
+                    recipient_rewards.push(RewardDestination::Missing(reward_info.clone()));
+                    continue;
+                }
+                // Basket shares of an account that cannot receive stay in the asset's pool.
+                RewardAssetKind::Token => is_receivable_token_account(
+                    reward_info,
+                    &asset.asset.token_program,
+                    &asset.asset.reward_mint,
+                    &user_pref.owner,
+                ),
+                // SOL is paid straight to the holder's wallet.
+                RewardAssetKind::NativeSol => reward_info.key() == user_pref.owner && reward_info.is_writable,
+            };
+            recipient_rewards.push(if receivable {
+                RewardDestination::Receivable(reward_info.clone())
+            } else {
+                RewardDestination::Unavailable
+            });
+        }
 
-        recipients.push(recipient_token_info.to_account_info());
-        new_last_paid = user_pref.owner;
+        recipients.push(Recipient {
+            owner: user_pref.owner,
+            preferences: pref_info,
+            token_account: recipient_token_info.to_account_info(),
+            balance,
+            delivery,
+            create_base: can_create && recipient_token_info.data_is_empty(),
+            rent_funded: false,
+            swap,
+            reward_accounts: recipient_rewards,
+        });
     }
 
     if recipients.is_empty() {
-        // If nothing exists after the current cursor, reset for next cycle.
-        if distribution_config.last_paid != Pubkey::default() {
-            distribution_config.last_paid = Pubkey::default();
-            distribution_config.updated_at = Clock::get()?.unix_timestamp;
-            msg!("No eligible accounts after cursor; last_paid reset for next cycle");
-            return Ok(());
+        // Every holder of the batch was passed over; the cursor still moves on.
+        distribution_config.last_reflect_at = now;
+        distribution_config.updated_at = now;
+        msg!(
+            "Cycle {}: no eligible holders in batch, cursor={} of {}",
+            distribution_config.cycle_id,
+            distribution_config.holder_cursor,
+            distribution_config.cycle_holders
+        );
+        if distribution_config.sweep_complete() {
+            distribution_config.end_cycle();
         }
-        return Err(crate::errors::SolFlexError::NoEligibleAccounts.into());
+        return Ok(());
     }
 
-    // Keep the existing "10% per run" throttle, then split across this batch.
-    let amount_to_distribute = distribution_config.reflection_pool / 10;
-    let per_recipient_amount = amount_to_distribute / recipients.len() as u64;
-    require!(
-        per_recipient_amount >= config.min_reflection_per_account,
-        crate::errors::SolFlexError::InvalidParameters
-    );
+    let mut payouts: Vec<u64> = Vec::with_capacity(recipients.len());
+    let mut allocated: u64 = 0;
+    let mut net_allocated: u64 = 0;
+    let mut dust_fraction: u128 = 0;
+    let mut pending_total: u64 = 0;
+    for recipient in recipients.iter_mut() {
+        let payout = distribution_config.cycle_payout(recipient.balance, allocated)?;
+        // Payouts are floored; recipients whose payout (net of any Token-2022 transfer fee)
+        // falls below the per-account minimum, or past the cycle budget, are passed over
+        // and their share stays in the pool.
+        let net_payout = net_transfer_amount(&ctx.accounts.token_mint.to_account_info(), payout)?;
+        // A missing base account is only created once a payout is due to it; if the
+        // reserve cannot create it, the share is held (or skipped) as before.
+        if recipient.create_base && payout > 0 && net_payout >= config.min_reflection_per_account {
+            if let Some(creator) = ata_creator.as_mut() {
+                if creator.create(
+                    &recipient.token_account,
+                    &recipient.owner,
+                    &ctx.accounts.token_mint.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                )? {
+                    recipient.rent_funded = true;
+                    if is_receivable_token_account(
+                        &recipient.token_account,
+                        &ctx.accounts.token_program.key(),
+                        &distribution_config.token_mint,
+                        &recipient.owner,
+                    ) {
+                        recipient.delivery = Delivery::Pay;
+                    }
+                }
+            }
+        }
+        let (mut payout, mut net_payout) = if payout > 0
+            && net_payout >= config.min_reflection_per_account
+            && recipient.delivery != Delivery::Skip
+        {
+            (payout, net_payout)
+        } else {
+            (0, 0)
+        };
+        // Shares of recipients that cannot receive are held for claim_pending. A holder
+        // whose pending slots are full is passed over like any other unpaid recipient.
+        if payout > 0 && recipient.delivery == Delivery::Hold {
+            let mut user_pref: Account<UserPreferences> = Account::try_from(recipient.preferences)?;
+            if user_pref.accrue_pending(distribution_config.key(), payout)? {
+                user_pref.exit(&crate::ID)?;
+                pending_total += payout;
+            } else {
+                (payout, net_payout) = (0, 0);
+            }
+        }
+        if payout > 0 {
+            dust_fraction += distribution_config.payout_dust_fraction(recipient.balance)?;
+        }
+        allocated += payout;
+        net_allocated += net_payout;
+        payouts.push(payout);
+    }
 
-    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, &[distribution_config.bump]];
-    for recipient in recipients.iter() {
-        let cpi_accounts = Transfer {
+    let token_mint_key = distribution_config.token_mint;
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+    let mut paid_count: usize = 0;
+    for (recipient, amount) in recipients.iter().zip(payouts.iter()) {
+        if *amount == 0 || recipient.swap.is_some() || recipient.delivery != Delivery::Pay {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
             from: fee_vault.to_account_info(),
-            to: recipient.clone(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: recipient.token_account.clone(),
             authority: distribution_config.to_account_info(),
         };
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 cpi_accounts,
                 &[signer_seeds],
             ),
-            per_recipient_amount,
+            *amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        paid_count += 1;
+    }
+
+    // Recipients sharing a pool are swapped together: one CPI per pool into the
+    // program-owned intermediate account, then the output is split pro-rata to
+    // each recipient's base-asset payout (the rounding remainder goes to the last one).
+    for (index, (pool_id, min_out_rate, route, _)) in routes.iter().enumerate() {
+        let group: Vec<(&AccountInfo<'info>, u64)> = recipients
+            .iter()
+            .zip(payouts.iter())
+            .filter_map(|(recipient, amount)| match &recipient.swap {
+                Some((route_index, destination)) if *route_index == index && *amount > 0 => {
+                    Some((destination, *amount))
+                }
+                _ => None,
+            })
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+
+        let amount_in = group.iter().try_fold(0u64, |acc, (_, amount)| {
+            acc.checked_add(*amount)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)
+        })?;
+        let minimum_amount_out = SwapRouteAccounts::minimum_amount_out(amount_in, *min_out_rate)?;
+        let vault_before = token_account_amount(&fee_vault.to_account_info())?;
+        let intermediate_before = token_account_amount(&route.intermediate)?;
+        route.swap(
+            &fee_vault.to_account_info(),
+            &distribution_config.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            amount_in,
+            minimum_amount_out,
+            &[signer_seeds],
         )?;
+        // Do not trust the adapter: check what actually left the vault and arrived.
+        let vault_spent = vault_before.saturating_sub(token_account_amount(&fee_vault.to_account_info())?);
+        require!(vault_spent == amount_in, crate::errors::SolFlexError::InvalidSwapRoute);
+        let amount_out = token_account_amount(&route.intermediate)?.saturating_sub(intermediate_before);
+        require!(
+            amount_out >= minimum_amount_out,
+            crate::errors::SolFlexError::SlippageExceeded
+        );
+
+        let mut remaining_out = amount_out;
+        for (position, (destination, amount)) in group.iter().enumerate() {
+            let share = if position + 1 == group.len() {
+                remaining_out
+            } else {
+                u64::try_from(amount_out as u128 * *amount as u128 / amount_in as u128)
+                    .map_err(|_| crate::errors::SolFlexError::ArithmeticOverflow)?
+            };
+            if share > 0 {
+                route.transfer_out(destination, &distribution_config.to_account_info(), share, &[signer_seeds])?;
+            }
+            remaining_out -= share;
+            paid_count += 1;
+        }
+
+        msg!(
+            "Pool {}: swapped {} in for {} out across {} recipients",
+            pool_id,
+            amount_in,
+            amount_out,
+            group.len()
+        );
+    }
+
+    distribution_config.accrue_pending(pending_total)?;
+
+    // Basket assets follow the base cycle: each snapshots its own budget and rate the
+    // first time it is seen in a cycle, then pays every recipient of the batch directly.
+    for (asset_index, basket_asset) in basket.iter_mut().enumerate() {
+        basket_asset.asset.sync_cycle(distribution_config, ctx.accounts.token_mint.supply)?;
+        let mut asset_allocated: u64 = 0;
+        let mut asset_paid: usize = 0;
+        for recipient in recipients.iter_mut() {
+            let payout = basket_asset.asset.cycle_payout(
+                distribution_config.distribution_mode,
+                recipient.balance,
+                asset_allocated,
+            )?;
+            if payout == 0 {
+                continue;
+            }
+            if basket_asset.asset.kind == RewardAssetKind::Token
+                && net_transfer_amount(&basket_asset.mint, payout)? < basket_asset.asset.min_payout
+            {
+                continue;
+            }
+            let destination = match &recipient.reward_accounts[asset_index] {
+                RewardDestination::Receivable(destination) => destination.clone(),
+                RewardDestination::Missing(destination) => {
+                    let Some(creator) = ata_creator.as_mut() else {
+                        continue;
+                    };
+                    if !creator.create(destination, &recipient.owner, &basket_asset.mint, &basket_asset.token_program)? {
+                        continue;
+                    }
+                    recipient.rent_funded = true;
+                    if !is_receivable_token_account(
+                        destination,
+                        &basket_asset.asset.token_program,
+                        &basket_asset.asset.reward_mint,
+                        &recipient.owner,
+                    ) {
+                        continue;
+                    }
+                    destination.clone()
+                }
+                RewardDestination::Unavailable => continue,
+            };
+            match basket_asset.asset.kind {
+                RewardAssetKind::Token => {
+                    token_interface::transfer_checked(
+                        CpiContext::new_with_signer(
+                            basket_asset.token_program.clone(),
+                            TransferChecked {
+                                from: basket_asset.vault.clone(),
+                                mint: basket_asset.mint.clone(),
+                                to: destination.clone(),
+                                authority: distribution_config.to_account_info(),
+                            },
+                            &[signer_seeds],
+                        ),
+                        payout,
+                        basket_asset.decimals,
+                    )?;
+                }
+                RewardAssetKind::NativeSol => {
+                    // A wallet that would end up below the rent-exempt minimum cannot be credited.
+                    let wallet_after = destination
+                        .lamports()
+                        .checked_add(payout)
+                        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+                    if payout < basket_asset.asset.min_payout || wallet_after < rent.minimum_balance(0) {
+                        continue;
+                    }
+                    // The vault is this program's own PDA, so lamports move without a CPI.
+                    **basket_asset.vault.try_borrow_mut_lamports()? -= payout;
+                    **destination.try_borrow_mut_lamports()? += payout;
+                }
+            }
+            asset_allocated += payout;
+            asset_paid += 1;
+        }
+        basket_asset.asset.distribute(asset_allocated)?;
+        basket_asset.asset.exit(&crate::ID)?;
+        msg!(
+            "Reward asset {}: distributed {} to {} holders",
+            basket_asset.asset.reward_mint,
+            asset_allocated,
+            asset_paid
+        );
+    }
+
+    // Owners whose accounts the reserve paid for are never funded again.
+    for recipient in recipients.iter().filter(|recipient| recipient.rent_funded) {
+        let mut user_pref: Account<UserPreferences> = Account::try_from(recipient.preferences)?;
+        user_pref.rent_funded = true;
+        user_pref.exit(&crate::ID)?;
     }
 
-    let distributed_total = per_recipient_amount * recipients.len() as u64;
-    distribution_config.distribute_reflection(distributed_total)?;
+    let distributed_total = payouts.iter().try_fold(0u64, |acc, amount| {
+        acc.checked_add(*amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)
+    })?;
+    distribution_config.distribute_reflection(distributed_total - pending_total)?;
+    let batch_weight = recipients.iter().try_fold(0u64, |acc, recipient| {
+        acc.checked_add(recipient.balance)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)
+    })?;
+    distribution_config.record_cycle_batch(distributed_total, recipients.len() as u32, batch_weight, dust_fraction)?;
+
+    // Permissionless cranks are paid from what remains in the reflection pool.
+    if !is_cranker && distributed_total > 0 {
+        let crank_reward = distribution_config.crank_reward_amount(distributed_total)?;
+        if crank_reward > 0 {
+            let crank_token_account = ctx
+                .accounts
+                .crank_token_account
+                .as_ref()
+                .ok_or(crate::errors::SolFlexError::InvalidTokenAccount)?;
+            let crank_cpi_accounts = TransferChecked {
+                from: fee_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: crank_token_account.to_account_info(),
+                authority: distribution_config.to_account_info(),
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    crank_cpi_accounts,
+                    &[signer_seeds],
+                ),
+                crank_reward,
+                ctx.accounts.token_mint.decimals,
+            )?;
+            distribution_config.pay_crank_reward(crank_reward)?;
+            msg!("Crank reward {} paid to {}", crank_reward, ctx.accounts.authority.key());
+        }
+    }
 
     // Dev pool payout is tracked separately and paid to the configured dev token account.
     if distribution_config.dev_pool > 0 {
         let dev_amount = distribution_config.dev_pool;
-        let dev_cpi_accounts = Transfer {
+        let dev_cpi_accounts = TransferChecked {
             from: fee_vault.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: dev_token_account.to_account_info(),
             authority: distribution_config.to_account_info(),
         };
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 dev_cpi_accounts,
                 &[signer_seeds],
             ),
             dev_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         distribution_config.distribute_dev(dev_amount)?;
     }
 
-    distribution_config.last_paid = new_last_paid;
-    distribution_config.updated_at = Clock::get()?.unix_timestamp;
+    if let Some(creator) = ata_creator.as_ref().filter(|creator| creator.spent > 0) {
+        msg!("Rent reserve spent {} lamports creating recipient accounts", creator.spent);
+    }
+
+    distribution_config.last_reflect_at = now;
+    distribution_config.updated_at = now;
 
     msg!(
-        "Distributed {} to {} holders, cursor={}",
-        per_recipient_amount,
+        "Cycle {}: distributed {} ({} net of transfer fees, {} held pending) to {} of {} holders, cursor={} of {}",
+        distribution_config.cycle_id,
+        distributed_total,
+        net_allocated,
+        pending_total,
+        paid_count,
         recipients.len(),
-        distribution_config.last_paid
+        distribution_config.holder_cursor,
+        distribution_config.cycle_holders
+    );
+    if distribution_config.sweep_complete() {
+        distribution_config.end_cycle();
+    }
+
+    Ok(())
+}
+
+// Snapshot sweep: records the balance of each holder in `pair_accounts`, in holder_index
+// order from the cursor, and snapshots the cycle's budget and rate once every holder has
+// been visited. Nothing is paid.
+fn snapshot_batch<'info>(
+    config: &Config,
+    distribution_config: &mut Account<'info, DistributionConfig>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    pair_accounts: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<()> {
+    let batch_limit = distribution_config.limit as usize;
+    let mut recorded: u32 = 0;
+    for pair in pair_accounts.chunks_exact(2).take(batch_limit) {
+        if distribution_config.sweep_complete() {
+            break;
+        }
+        let pref_info = &pair[0];
+        let mut user_pref: Account<UserPreferences> = Account::try_from(pref_info)?;
+        require!(
+            UserPreferences::is_current_layout(pref_info),
+            crate::errors::SolFlexError::AccountNotMigrated
+        );
+        // The snapshot is written to the holder's preferences.
+        require!(pref_info.is_writable, crate::errors::SolFlexError::InvalidRemainingAccounts);
+        distribution_config.advance_cursor(user_pref.holder_index)?;
+        if user_pref.is_banned || config.is_blocklisted(user_pref.owner) {
+            continue;
+        }
+
+        // Only holders with a balance are counted; a missing or mismatched account has no
+        // trusted balance, so registering empty wallets cannot dilute an Equal cycle.
+        let balance = InterfaceAccount::<TokenAccount>::try_from(&pair[1])
+            .ok()
+            .filter(|token| token.mint == distribution_config.token_mint && token.owner == user_pref.owner)
+            .map_or(0, |token| token.amount);
+        if balance == 0 {
+            continue;
+        }
+        if !user_pref.record_snapshot(distribution_config.key(), distribution_config.cycle_id, balance) {
+            msg!("No snapshot slot free for {}; it is not paid this cycle", user_pref.owner);
+            continue;
+        }
+        user_pref.exit(&crate::ID)?;
+        distribution_config.count_eligible(true)?;
+        recorded += 1;
+    }
+
+    distribution_config.last_reflect_at = now;
+    distribution_config.updated_at = now;
+    msg!(
+        "Cycle {}: snapshotted {} holders, cursor={} of {}",
+        distribution_config.cycle_id,
+        recorded,
+        distribution_config.holder_cursor,
+        distribution_config.cycle_holders
     );
 
+    // Snapshot the throttled cycle budget and rate once the sweep is complete; a cycle
+    // without a single eligible holder closes without paying.
+    if distribution_config.sweep_complete() && distribution_config.cycle_eligible == 0 {
+        distribution_config.end_cycle();
+        msg!("Cycle {} closed: no eligible holders", distribution_config.cycle_id);
+    } else if distribution_config.sweep_complete() {
+        let cycle_budget = if distribution_config.reflection_pool >= config.min_reflection_pool {
+            distribution_config.throttled_budget()?
+        } else {
+            0
+        };
+        let holders = distribution_config.cycle_eligible;
+        distribution_config.start_cycle(cycle_budget, token_mint.supply, holders)?;
+        msg!(
+            "Started payout of cycle {}: budget={}, rate={}",
+            distribution_config.cycle_id,
+            distribution_config.cycle_budget,
+            distribution_config.cycle_rate
+        );
+        if distribution_config.distribution_mode == DistributionMode::Equal && cycle_budget > 0 {
+            require!(
+                distribution_config.cycle_rate >= config.min_reflection_per_account as u128,
+                crate::errors::SolFlexError::InvalidParameters
+            );
+        }
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
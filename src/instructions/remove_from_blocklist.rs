@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::Config;
+use crate::state::{AdminAction, Config, Proposal};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RemoveFromBlocklistParams {
@@ -12,19 +12,31 @@ pub struct RemoveFromBlocklist<'info> {
         mut,
         seeds = [Config::SEED_PREFIX],
         bump = config.bump,
-        has_one = authority
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<RemoveFromBlocklist>, params: RemoveFromBlocklistParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::RemoveFromBlocklist,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
     let config = &mut ctx.accounts.config;
 
     config.remove_from_blocklist(params.account_to_unblock)?;
 
-    msg!("Account {} removed from blocklist by authority: {}", params.account_to_unblock, ctx.accounts.authority.key());
+    msg!("Account {} removed from blocklist by: {}", params.account_to_unblock, ctx.accounts.authority.key());
     Ok(())
 }
\ No newline at end of file
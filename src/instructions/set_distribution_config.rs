@@ -1,17 +1,54 @@
 use anchor_lang::prelude::*;
-use crate::state::{DistributionConfig, Config};
+use crate::state::{AdminAction, CrankReward, DistributionConfig, DistributionMode, DustPolicy, Proposal, ReflectThrottle, Config};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SetDistributionConfigParams {
     pub token_mint: Pubkey,
     pub fee_vault: Pubkey,
     pub limit: u32,
+    pub distribution_mode: DistributionMode,
+    pub throttle: ReflectThrottle,
     pub reflection_rate: u16,
     pub burn_rate: u16,
     pub project_rate: u16,
     pub project_account: Pubkey,
     pub dev_fee_rate: u16,
     pub dev_account: Pubkey,
+    pub permissionless_crank: bool,
+    pub crank_reward: CrankReward,
+    pub max_crank_reward_bps: u16, // Cap on crank rewards per cycle, in basis points of the cycle budget
+    pub dust_policy: DustPolicy,
+    pub auto_create_accounts: bool,
+    pub max_rent_per_batch: u64,
+}
+
+impl SetDistributionConfigParams {
+    /// With a timelock, an existing distribution's rates, accounts, fee vault, mode, throttle,
+    /// crank, dust and account creation settings only change through queue_config_change.
+    pub fn require_untimelocked(&self, distribution: &DistributionConfig, timelock_delay: i64) -> Result<()> {
+        if timelock_delay == 0 {
+            return Ok(());
+        }
+        require!(
+            distribution.fee_vault == self.fee_vault
+                && distribution.reflection_rate == self.reflection_rate
+                && distribution.burn_rate == self.burn_rate
+                && distribution.project_rate == self.project_rate
+                && distribution.project_account == self.project_account
+                && distribution.dev_fee_rate == self.dev_fee_rate
+                && distribution.dev_account == self.dev_account
+                && distribution.distribution_mode == self.distribution_mode
+                && distribution.throttle == self.throttle
+                && distribution.permissionless_crank == self.permissionless_crank
+                && distribution.crank_reward == self.crank_reward
+                && distribution.max_crank_reward_bps == self.max_crank_reward_bps
+                && distribution.dust_policy == self.dust_policy
+                && distribution.auto_create_accounts == self.auto_create_accounts
+                && distribution.max_rent_per_batch == self.max_rent_per_batch,
+            crate::errors::SolFlexError::TimelockRequired
+        );
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -19,7 +56,8 @@ pub struct SetDistributionConfigParams {
 pub struct SetDistributionConfig<'info> {
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
@@ -27,11 +65,15 @@ pub struct SetDistributionConfig<'info> {
         init_if_needed,
         payer = authority,
         space = 8 + DistributionConfig::INIT_SPACE,
-        seeds = [DistributionConfig::SEED_PREFIX],
+        seeds = [DistributionConfig::SEED_PREFIX, params.token_mint.as_ref()],
         bump
     )]
     pub distribution_config: Account<'info, DistributionConfig>,
 
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -39,16 +81,24 @@ pub struct SetDistributionConfig<'info> {
 }
 
 pub fn handler(ctx: Context<SetDistributionConfig>, params: SetDistributionConfigParams) -> Result<()> {
-    let config = &ctx.accounts.config;
-    let distribution_config = &mut ctx.accounts.distribution_config;
-    let authority = &ctx.accounts.authority;
-
     // Check authorization
-    require!(authority.key() == config.authority, crate::errors::SolFlexError::Unauthorized);
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::SetDistributionConfig,
+        &[ctx.accounts.config.key(), ctx.accounts.distribution_config.key()],
+        &params,
+    )?;
+
+    let distribution_config = &mut ctx.accounts.distribution_config;
 
     // Validate parameters
     require!(params.limit > 0 && params.limit <= 1000, crate::errors::SolFlexError::InvalidParameters);
 
+    if distribution_config.created_at != 0 {
+        params.require_untimelocked(distribution_config, ctx.accounts.config.timelock_delay)?;
+    }
+
     // Initialize once for first creation; afterwards this instruction updates in-place.
     if distribution_config.created_at == 0 {
         let bump = ctx.bumps.distribution_config;
@@ -65,12 +115,20 @@ pub fn handler(ctx: Context<SetDistributionConfig>, params: SetDistributionConfi
     distribution_config.token_mint = params.token_mint;
     distribution_config.fee_vault = params.fee_vault;
     distribution_config.limit = params.limit;
+    distribution_config.set_distribution_mode(params.distribution_mode)?;
+    distribution_config.throttle = params.throttle;
     distribution_config.reflection_rate = params.reflection_rate;
     distribution_config.burn_rate = params.burn_rate;
     distribution_config.project_rate = params.project_rate;
     distribution_config.project_account = params.project_account;
     distribution_config.dev_fee_rate = params.dev_fee_rate;
     distribution_config.dev_account = params.dev_account;
+    distribution_config.permissionless_crank = params.permissionless_crank;
+    distribution_config.crank_reward = params.crank_reward;
+    distribution_config.max_crank_reward_bps = params.max_crank_reward_bps;
+    distribution_config.dust_policy = params.dust_policy;
+    distribution_config.auto_create_accounts = params.auto_create_accounts;
+    distribution_config.max_rent_per_batch = params.max_rent_per_batch;
     distribution_config.updated_at = Clock::get().unwrap().unix_timestamp;
 
     // Validate rates
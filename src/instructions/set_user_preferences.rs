@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Config, UserPreferences};
+use crate::state::{Config, PauseScope, Role, UserPreferences};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SetUserPreferencesParams {
@@ -11,8 +11,10 @@ pub struct SetUserPreferencesParams {
 #[instruction(params: SetUserPreferencesParams)]
 pub struct SetUserPreferences<'info> {
     #[account(
+        mut,
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
@@ -21,7 +23,8 @@ pub struct SetUserPreferences<'info> {
         payer = authority,
         space = 8 + UserPreferences::INIT_SPACE,
         seeds = [UserPreferences::SEED_PREFIX, user.key().as_ref()],
-        bump
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub user_preferences: Account<'info, UserPreferences>,
 
@@ -35,23 +38,29 @@ pub struct SetUserPreferences<'info> {
 }
 
 pub fn handler(ctx: Context<SetUserPreferences>, params: SetUserPreferencesParams) -> Result<()> {
-    let config = &ctx.accounts.config;
+    ctx.accounts.config.require_not_paused(PauseScope::Preferences)?;
+
+    let config = &mut ctx.accounts.config;
     let user_preferences = &mut ctx.accounts.user_preferences;
     let user = &ctx.accounts.user;
     let authority = &ctx.accounts.authority;
 
-    // Either the user or program authority can set/update preferences.
+    // Either the user or an admin can set/update preferences.
     require!(
-        authority.key() == user.key() || authority.key() == config.authority,
+        authority.key() == user.key() || config.has_role(authority.key(), Role::Admin),
         crate::errors::SolFlexError::Unauthorized
     );
 
     // Validate parameters
     require!(params.custom_memo.len() <= 200, crate::errors::SolFlexError::InvalidMemoLength);
 
-    // Initialize the user preferences
+    // Initialize the user preferences; every new holder joins the registry reflect sweeps.
     if user_preferences.owner == Pubkey::default() {
-        **user_preferences = UserPreferences::new(user.key());
+        **user_preferences = UserPreferences::new(user.key(), config.holder_count);
+        config.holder_count = config
+            .holder_count
+            .checked_add(1)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
     }
 
     // Update preferences
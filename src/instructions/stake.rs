@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, DistributionConfig, DistributionMode, HolderRewards, PauseScope, UserPreferences};
+use crate::token_utils::token_account_amount;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StakeParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        seeds = [UserPreferences::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub user_preferences: Account<'info, UserPreferences>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + HolderRewards::INIT_SPACE,
+        seeds = [
+            HolderRewards::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub holder_rewards: Account<'info, HolderRewards>,
+
+    /// Holds every staked token of this distribution; owned by the distribution PDA.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [crate::constants::STAKE_VAULT_SEED, distribution_config.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = distribution_config,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn stake_handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
+    ctx.accounts.config.require_not_paused(PauseScope::Reflect)?;
+
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let holder_rewards = &mut ctx.accounts.holder_rewards;
+    let owner = ctx.accounts.owner.key();
+
+    require!(
+        distribution_config.distribution_mode == DistributionMode::Accumulator,
+        crate::errors::SolFlexError::InvalidConfig
+    );
+    require!(params.amount > 0, crate::errors::SolFlexError::InvalidParameters);
+    require!(
+        !ctx.accounts.user_preferences.is_banned && !config.is_blocklisted(owner),
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    if holder_rewards.created_at == 0 {
+        **holder_rewards = HolderRewards::new(distribution_config.key(), owner, ctx.bumps.holder_rewards);
+    }
+
+    // Shares are credited with what actually arrived, net of any transfer fee.
+    let stake_vault_info = ctx.accounts.stake_vault.to_account_info();
+    let vault_before = token_account_amount(&stake_vault_info)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: stake_vault_info.clone(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        params.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let received = token_account_amount(&stake_vault_info)?.saturating_sub(vault_before);
+
+    holder_rewards.staked = holder_rewards
+        .staked
+        .checked_add(received)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+    let staked = holder_rewards.staked;
+    distribution_config.checkpoint_shares(holder_rewards, staked)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    distribution_config.updated_at = now;
+    holder_rewards.updated_at = now;
+
+    msg!("Staked {} for {}, shares={}", received, owner, staked);
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    #[account(
+        seeds = [UserPreferences::SEED_PREFIX, owner.key().as_ref()],
+        bump,
+        constraint = UserPreferences::is_current_layout(&user_preferences.to_account_info()) @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub user_preferences: Account<'info, UserPreferences>,
+
+    #[account(
+        mut,
+        seeds = [
+            HolderRewards::SEED_PREFIX,
+            distribution_config.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = holder_rewards.bump
+    )]
+    pub holder_rewards: Account<'info, HolderRewards>,
+
+    #[account(
+        mut,
+        seeds = [crate::constants::STAKE_VAULT_SEED, distribution_config.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == distribution_config.token_mint @ crate::errors::SolFlexError::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ crate::errors::SolFlexError::InvalidTokenAccount
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn unstake_handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let distribution_config = &mut ctx.accounts.distribution_config;
+    let holder_rewards = &mut ctx.accounts.holder_rewards;
+    let owner = ctx.accounts.owner.key();
+
+    // Principal always stays withdrawable, in any mode and even while banned or paused.
+    require!(
+        params.amount > 0 && params.amount <= holder_rewards.staked,
+        crate::errors::SolFlexError::InvalidParameters
+    );
+
+    holder_rewards.staked -= params.amount;
+    let eligible = !ctx.accounts.user_preferences.is_banned && !config.is_blocklisted(owner);
+    let new_shares = if eligible { holder_rewards.staked } else { 0 };
+    distribution_config.checkpoint_shares(holder_rewards, new_shares)?;
+
+    let token_mint_key = distribution_config.token_mint;
+    let signer_seeds: &[&[u8]] = &[DistributionConfig::SEED_PREFIX, token_mint_key.as_ref(), &[distribution_config.bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: distribution_config.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        params.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    distribution_config.updated_at = now;
+    holder_rewards.updated_at = now;
+
+    msg!("Unstaked {} for {}, shares={}", params.amount, owner, new_shares);
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Config, ConfigChange, DistributionConfig, PendingConfigChange, Proposal};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueueConfigChangeParams {
+    pub change: ConfigChange,
+}
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Target distribution; required for rate, account and fee vault changes.
+    #[account(
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Option<Account<'info, DistributionConfig>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingConfigChange::INIT_SPACE,
+        seeds = [PendingConfigChange::SEED_PREFIX, config.change_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_config_change_handler(ctx: Context<QueueConfigChange>, params: QueueConfigChangeParams) -> Result<()> {
+    let signer = ctx.accounts.authority.key();
+    // The distribution is bound into the proposal even when absent, so an approval for one
+    // distribution cannot be queued against another.
+    let distribution = ctx.accounts.distribution_config.as_ref().map_or(Pubkey::default(), |account| account.key());
+    ctx.accounts.config.authorize_admin(
+        signer,
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::QueueConfigChange,
+        &[ctx.accounts.config.key(), distribution],
+        &params,
+    )?;
+
+    let change = params.change;
+    change.validate()?;
+
+    let config = &mut ctx.accounts.config;
+    let target = if change.targets_distribution() {
+        ctx.accounts
+            .distribution_config
+            .as_ref()
+            .ok_or(crate::errors::SolFlexError::InvalidConfig)?
+            .key()
+    } else {
+        // Authority and delay changes stay with the authority unless a multisig approved them.
+        require!(
+            config.threshold > 0 || signer == config.authority,
+            crate::errors::SolFlexError::Unauthorized
+        );
+        config.key()
+    };
+
+    let id = config.change_count;
+    *ctx.accounts.pending_change = PendingConfigChange::new(
+        id,
+        target,
+        change,
+        signer,
+        config.timelock_delay,
+        ctx.bumps.pending_change,
+    )?;
+    config.change_count = id
+        .checked_add(1)
+        .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+    msg!(
+        "Config change {} queued for {}: {:?}, executable at {}",
+        id,
+        target,
+        change,
+        ctx.accounts.pending_change.executable_at
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Target distribution; required for rate, account and fee vault changes.
+    #[account(
+        mut,
+        seeds = [DistributionConfig::SEED_PREFIX, distribution_config.token_mint.as_ref()],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Option<Account<'info, DistributionConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PendingConfigChange::SEED_PREFIX, pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        close = queued_by
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    #[account(
+        mut,
+        address = pending_change.queued_by @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub queued_by: SystemAccount<'info>,
+
+    /// Any signer may execute a change once its delay has elapsed.
+    pub executor: Signer<'info>,
+}
+
+pub fn execute_config_change_handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let pending_change = &ctx.accounts.pending_change;
+    let change = pending_change.change;
+
+    require!(
+        Clock::get()?.unix_timestamp >= pending_change.executable_at,
+        crate::errors::SolFlexError::TimelockNotElapsed
+    );
+
+    if change.targets_distribution() {
+        let distribution_config = ctx
+            .accounts
+            .distribution_config
+            .as_mut()
+            .ok_or(crate::errors::SolFlexError::InvalidConfig)?;
+        require!(
+            distribution_config.key() == pending_change.target,
+            crate::errors::SolFlexError::InvalidConfig
+        );
+        change.apply_to_distribution(distribution_config)?;
+    } else {
+        change.apply_to_config(&mut ctx.accounts.config)?;
+    }
+
+    msg!("Config change {} executed for {}: {:?}", pending_change.id, pending_change.target, change);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [PendingConfigChange::SEED_PREFIX, pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        close = queued_by
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    #[account(
+        mut,
+        address = pending_change.queued_by @ crate::errors::SolFlexError::InvalidParameters
+    )]
+    pub queued_by: SystemAccount<'info>,
+
+    /// Approved CancelConfigChange proposal; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_config_change_handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let pending_change = &ctx.accounts.pending_change;
+    let signer = ctx.accounts.authority.key();
+
+    // A change can be cancelled at any time before it is executed, under the same approval
+    // as queueing one: the multisig threshold once it governs, otherwise an Admin (the
+    // authority for authority and delay changes).
+    ctx.accounts.config.authorize_admin(
+        signer,
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::CancelConfigChange,
+        &[ctx.accounts.config.key(), pending_change.key()],
+        &pending_change.id,
+    )?;
+    let config = &ctx.accounts.config;
+    require!(
+        config.threshold > 0 || pending_change.change.targets_distribution() || signer == config.authority,
+        crate::errors::SolFlexError::Unauthorized
+    );
+
+    msg!("Config change {} cancelled by: {}", pending_change.id, signer);
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Config, GlobalTokenPools, Proposal};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAuthorityParams {
+    pub new_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn propose_authority_handler(ctx: Context<ProposeAuthority>, params: ProposeAuthorityParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::ProposeAuthority,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+
+    // With a timelock, transfers are queued as ConfigChange::Authority instead.
+    require!(config.timelock_delay == 0, crate::errors::SolFlexError::TimelockRequired);
+    config.propose_authority(params.new_authority)?;
+
+    msg!("Authority transfer to {} proposed by: {}", params.new_authority, ctx.accounts.authority.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [GlobalTokenPools::SEED_PREFIX],
+        bump = global_pools.bump
+    )]
+    pub global_pools: Account<'info, GlobalTokenPools>,
+
+    /// Must match `config.pending_authority`.
+    pub new_authority: Signer<'info>,
+}
+
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let global_pools = &mut ctx.accounts.global_pools;
+    let previous_authority = config.authority;
+
+    config.accept_authority(ctx.accounts.new_authority.key())?;
+
+    // Keep the pool registry's authority in step with the program authority.
+    global_pools.authority = config.authority;
+    global_pools.updated_at = config.updated_at;
+
+    msg!("Authority transferred from {} to {}", previous_authority, config.authority);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_authority_transfer_handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::CancelAuthorityTransfer,
+        &[ctx.accounts.config.key()],
+        &(),
+    )?;
+
+    let config = &mut ctx.accounts.config;
+
+    config.cancel_authority_transfer()?;
+
+    msg!("Authority transfer cancelled by: {}", ctx.accounts.authority.key());
+    Ok(())
+}
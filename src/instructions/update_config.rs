@@ -1,9 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::Config;
+use crate::state::{AdminAction, Config, Proposal};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UpdateConfigParams {
-    pub authority: Pubkey,
     pub min_reflection_pool: u64,
     pub min_reflection_per_account: u64,
 }
@@ -14,22 +13,32 @@ pub struct UpdateConfig<'info> {
         mut,
         seeds = [Config::SEED_PREFIX],
         bump = config.bump,
-        has_one = authority
+        constraint = config.version == crate::constants::CONFIG_VERSION @ crate::errors::SolFlexError::AccountNotMigrated
     )]
     pub config: Account<'info, Config>,
 
+    /// Approved proposal for this call; required once a multisig is configured.
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    ctx.accounts.config.authorize_admin(
+        ctx.accounts.authority.key(),
+        ctx.accounts.proposal.as_deref_mut(),
+        AdminAction::UpdateConfig,
+        &[ctx.accounts.config.key()],
+        &params,
+    )?;
+
     let config = &mut ctx.accounts.config;
 
-    config.authority = params.authority;
-    config.min_reflection_pool = params.min_reflection_pool;
-    config.min_reflection_per_account = params.min_reflection_per_account;
-    config.updated_at = Clock::get().unwrap().unix_timestamp;
+    // The authority is changed only through propose_authority / accept_authority.
+    config.update_config(params.min_reflection_pool, params.min_reflection_per_account)?;
 
-    msg!("Config updated by authority: {}", ctx.accounts.authority.key());
+    msg!("Config updated by: {}", ctx.accounts.authority.key());
     Ok(())
 }
\ No newline at end of file
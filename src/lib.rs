@@ -5,6 +5,8 @@ pub mod constants;
 pub mod errors;
 pub mod instructions;
 pub mod state;
+pub mod swap_adapter;
+pub mod token_utils;
 
 declare_id!("5im5SdEc2dg63B5C9vm83mwQqxGUAphG2K47uGgA69ZS");
 
@@ -32,8 +34,8 @@ pub mod sol_flex {
         instructions::remove_from_blocklist::handler(ctx, params)
     }
 
-    pub fn reflect(ctx: Context<Reflect>) -> Result<()> {
-        instructions::reflect::handler(ctx)
+    pub fn reflect<'info>(ctx: Context<'_, '_, 'info, 'info, Reflect<'info>>, params: ReflectParams) -> Result<()> {
+        instructions::reflect::handler(ctx, params)
     }
 
     pub fn set_user_preferences(ctx: Context<SetUserPreferences>, params: SetUserPreferencesParams) -> Result<()> {
@@ -59,4 +61,144 @@ pub mod sol_flex {
     pub fn record_fees(ctx: Context<RecordFees>, params: RecordFeesParams) -> Result<()> {
         instructions::record_fees::handler(ctx, params)
     }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        instructions::claim::handler(ctx)
+    }
+
+    pub fn stake(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
+        instructions::stake::stake_handler(ctx, params)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
+        instructions::stake::unstake_handler(ctx, params)
+    }
+
+    pub fn publish_distribution_root(ctx: Context<PublishDistributionRoot>, params: PublishDistributionRootParams) -> Result<()> {
+        instructions::publish_distribution_root::handler(ctx, params)
+    }
+
+    pub fn claim_reflection(ctx: Context<ClaimReflection>, params: ClaimReflectionParams) -> Result<()> {
+        instructions::claim_reflection::handler(ctx, params)
+    }
+
+    pub fn reclaim_epoch(ctx: Context<ReclaimEpoch>, params: ReclaimEpochParams) -> Result<()> {
+        instructions::reclaim_epoch::handler(ctx, params)
+    }
+
+    pub fn withdraw_project(ctx: Context<WithdrawProject>) -> Result<()> {
+        instructions::withdraw_project::handler(ctx)
+    }
+
+    pub fn harvest_withheld_fees<'info>(ctx: Context<'_, '_, 'info, 'info, HarvestWithheldFees<'info>>) -> Result<()> {
+        instructions::harvest_withheld_fees::handler(ctx)
+    }
+
+    pub fn add_depositor(ctx: Context<AddDepositor>, params: AddDepositorParams) -> Result<()> {
+        instructions::manage_depositors::add_depositor_handler(ctx, params)
+    }
+
+    pub fn remove_depositor(ctx: Context<RemoveDepositor>, params: RemoveDepositorParams) -> Result<()> {
+        instructions::manage_depositors::remove_depositor_handler(ctx, params)
+    }
+
+    pub fn deposit_fees(ctx: Context<DepositFees>, params: DepositFeesParams) -> Result<()> {
+        instructions::deposit_fees::handler(ctx, params)
+    }
+
+    pub fn reconcile(ctx: Context<Reconcile>, params: ReconcileParams) -> Result<()> {
+        instructions::reconcile::handler(ctx, params)
+    }
+
+    pub fn add_reward_asset(ctx: Context<AddRewardAsset>, params: AddRewardAssetParams) -> Result<()> {
+        instructions::manage_reward_assets::add_reward_asset_handler(ctx, params)
+    }
+
+    pub fn add_native_reward_asset(ctx: Context<AddNativeRewardAsset>, params: AddRewardAssetParams) -> Result<()> {
+        instructions::manage_reward_assets::add_native_reward_asset_handler(ctx, params)
+    }
+
+    pub fn remove_reward_asset(ctx: Context<RemoveRewardAsset>) -> Result<()> {
+        instructions::manage_reward_assets::remove_reward_asset_handler(ctx)
+    }
+
+    pub fn deposit_reward(ctx: Context<DepositReward>, params: DepositRewardParams) -> Result<()> {
+        instructions::deposit_reward::handler(ctx, params)
+    }
+
+    pub fn deposit_sol_reward(ctx: Context<DepositSolReward>, params: DepositSolRewardParams) -> Result<()> {
+        instructions::deposit_sol_reward::handler(ctx, params)
+    }
+
+    pub fn claim_pending(ctx: Context<ClaimPending>) -> Result<()> {
+        instructions::claim_pending::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, params: ProposeAuthorityParams) -> Result<()> {
+        instructions::transfer_authority::propose_authority_handler(ctx, params)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::transfer_authority::accept_authority_handler(ctx)
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::transfer_authority::cancel_authority_transfer_handler(ctx)
+    }
+
+    pub fn set_multisig(ctx: Context<SetMultisig>, params: SetMultisigParams) -> Result<()> {
+        instructions::multisig::set_multisig_handler(ctx, params)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, params: CreateProposalParams) -> Result<()> {
+        instructions::multisig::create_proposal_handler(ctx, params)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::multisig::approve_proposal_handler(ctx)
+    }
+
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        instructions::multisig::close_proposal_handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::manage_roles::grant_role_handler(ctx, params)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::manage_roles::revoke_role_handler(ctx, params)
+    }
+
+    pub fn queue_config_change(ctx: Context<QueueConfigChange>, params: QueueConfigChangeParams) -> Result<()> {
+        instructions::timelock::queue_config_change_handler(ctx, params)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::timelock::execute_config_change_handler(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::timelock::cancel_config_change_handler(ctx)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, params: SetPauseParams) -> Result<()> {
+        instructions::pause::set_pause_handler(ctx, params)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, params: SetGuardianParams) -> Result<()> {
+        instructions::pause::set_guardian_handler(ctx, params)
+    }
+
+    pub fn migrate_legacy_distribution(ctx: Context<MigrateLegacyDistribution>) -> Result<()> {
+        instructions::migrate_legacy_distribution::handler(ctx)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_accounts::migrate_config_handler(ctx)
+    }
+
+    pub fn migrate_user_preferences(ctx: Context<MigrateUserPreferences>) -> Result<()> {
+        instructions::migrate_accounts::migrate_user_preferences_handler(ctx)
+    }
 }
\ No newline at end of file
@@ -1,10 +1,67 @@
 use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Proposal};
+
+/// Operator roles; `Config.authority` implicitly holds every role while no multisig is configured
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Role {
+    Admin, // update_config, set_distribution_config, depositors, reward assets, preferences on a user's behalf
+    Cranker, // reflect as a trusted crank, publish_distribution_root, claims on a holder's behalf
+    FeeRecorder, // record_fees, harvest_withheld_fees
+    BlocklistManager, // blocklist changes, ban_user
+    PoolManager, // add_pool, remove_pool
+    Treasurer, // withdraw_project, reconcile surplus crediting
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct RoleGrant {
+    pub account: Pubkey,
+    pub role: Role,
+}
+
+/// Handler groups that can be paused independently
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseScope {
+    Reflect, // reflect, claims, publish_distribution_root
+    Fees, // fee and reward deposits, record/harvest, withdraw_project
+    Preferences, // set_user_preferences, ban_user
+    Pools, // add_pool, remove_pool, reflect swap routes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug, Default)]
+pub struct PauseFlags {
+    pub all: bool, // Pauses every scope
+    pub reflect: bool,
+    pub fees: bool,
+    pub preferences: bool,
+    pub pools: bool,
+}
+
+impl PauseFlags {
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.all
+            || match scope {
+                PauseScope::Reflect => self.reflect,
+                PauseScope::Fees => self.fees,
+                PauseScope::Preferences => self.preferences,
+                PauseScope::Pools => self.pools,
+            }
+    }
+
+    /// True if every flag set in `other` is also set here.
+    pub fn contains(&self, other: &PauseFlags) -> bool {
+        (self.all || !other.all)
+            && (self.reflect || !other.reflect)
+            && (self.fees || !other.fees)
+            && (self.preferences || !other.preferences)
+            && (self.pools || !other.pools)
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
     pub authority: Pubkey,
-    pub version: u8,
+    pub version: u8, // Layout version; CONFIG_VERSION once migrate_config has run
     #[max_len(100)]
     pub blocklist: Vec<Pubkey>,
     pub is_initialized: bool,
@@ -13,6 +70,53 @@ pub struct Config {
     pub min_reflection_pool: u64, // Minimum reflection pool amount before sending
     pub min_reflection_per_account: u64, // Minimum per-account amount to activate reflections
     pub bump: u8, // Store PDA bump seed for efficiency
+    // Fields below were added after the first deployment; keep new ones appended.
+    pub pending_authority: Option<Pubkey>, // Proposed authority awaiting accept_authority
+    #[max_len(20)]
+    pub depositors: Vec<Pubkey>, // Allowlisted signers for deposit_fees (authority is always allowed)
+    #[max_len(10)]
+    pub signers: Vec<Pubkey>, // Multisig signer set; empty when the single authority governs
+    pub threshold: u8, // Approvals required on a Proposal; 0 disables the multisig
+    pub proposal_count: u64, // Next Proposal id
+    pub multisig_nonce: u64, // Bumped by set_multisig so proposals approved by a replaced signer set lapse
+    #[max_len(32)]
+    pub roles: Vec<RoleGrant>, // Operator role grants
+    pub timelock_delay: i64, // Seconds a queued PendingConfigChange waits; 0 leaves direct changes enabled
+    pub change_count: u64, // Next PendingConfigChange id
+    pub pause_flags: PauseFlags,
+    pub guardian: Option<Pubkey>, // May set pause flags but never clear them
+    pub holder_count: u64, // UserPreferences accounts registered; each reflect cycle sweeps holder_index 0..holder_count
+}
+
+/// Layout of `Config` before the fields appended after `bump`. Only read by `migrate_config`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub authority: Pubkey,
+    pub version: u8,
+    pub blocklist: Vec<Pubkey>,
+    pub is_initialized: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub min_reflection_pool: u64,
+    pub min_reflection_per_account: u64,
+    pub bump: u8,
+}
+
+impl LegacyConfig {
+    /// Reads a `Config` account still at the legacy layout version.
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == <Config as anchor_lang::Discriminator>::DISCRIMINATOR,
+            crate::errors::SolFlexError::InvalidConfig
+        );
+        let legacy = Self::deserialize(&mut &data[8..]).map_err(|_| crate::errors::SolFlexError::InvalidConfig)?;
+        require!(
+            legacy.version < crate::constants::CONFIG_VERSION,
+            crate::errors::SolFlexError::AlreadyMigrated
+        );
+        Ok(legacy)
+    }
 }
 
 impl Config {
@@ -22,7 +126,7 @@ impl Config {
         let clock = Clock::get().unwrap();
         Self {
             authority,
-            version: 1,
+            version: crate::constants::CONFIG_VERSION,
             blocklist: Vec::new(),
             is_initialized: true,
             created_at: clock.unix_timestamp,
@@ -30,6 +134,33 @@ impl Config {
             min_reflection_pool: 100000, // 100,000 tokens minimum in reflection pool
             min_reflection_per_account: 10000, // 10,000 tokens minimum per account
             bump,
+            pending_authority: None,
+            depositors: Vec::new(),
+            signers: Vec::new(),
+            threshold: 0,
+            proposal_count: 0,
+            multisig_nonce: 0,
+            roles: Vec::new(),
+            timelock_delay: 0,
+            change_count: 0,
+            pause_flags: PauseFlags::default(),
+            guardian: None,
+            holder_count: 0,
+        }
+    }
+
+    /// Rebuilds a legacy account at the current layout; appended fields take their defaults.
+    pub fn from_legacy(legacy: LegacyConfig) -> Self {
+        Self {
+            authority: legacy.authority,
+            version: crate::constants::CONFIG_VERSION,
+            blocklist: legacy.blocklist,
+            is_initialized: legacy.is_initialized,
+            created_at: legacy.created_at,
+            updated_at: Clock::get().unwrap().unix_timestamp,
+            min_reflection_pool: legacy.min_reflection_pool,
+            min_reflection_per_account: legacy.min_reflection_per_account,
+            ..Self::new(legacy.authority, legacy.bump)
         }
     }
 
@@ -55,11 +186,149 @@ impl Config {
         self.blocklist.contains(&account)
     }
 
-    pub fn update_config(&mut self, authority: Pubkey, min_reflection_pool: u64, min_reflection_per_account: u64) -> Result<()> {
-        self.authority = authority;
+    pub fn add_depositor(&mut self, depositor: Pubkey) -> Result<()> {
+        require!(!self.depositors.contains(&depositor), crate::errors::SolFlexError::DepositorAlreadyAllowed);
+        require!(self.depositors.len() < crate::constants::MAX_DEPOSITORS, crate::errors::SolFlexError::DepositorAllowlistFull);
+
+        self.depositors.push(depositor);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn remove_depositor(&mut self, depositor: Pubkey) -> Result<()> {
+        let position = self.depositors.iter().position(|&x| x == depositor)
+            .ok_or(crate::errors::SolFlexError::DepositorNotAllowed)?;
+
+        self.depositors.remove(position);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn is_depositor(&self, account: Pubkey) -> bool {
+        account == self.authority || self.depositors.contains(&account)
+    }
+
+    pub fn grant_role(&mut self, account: Pubkey, role: Role) -> Result<()> {
+        let grant = RoleGrant { account, role };
+        require!(!self.roles.contains(&grant), crate::errors::SolFlexError::RoleAlreadyGranted);
+        require!(self.roles.len() < crate::constants::MAX_ROLE_GRANTS, crate::errors::SolFlexError::RoleGrantsFull);
+
+        self.roles.push(grant);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn revoke_role(&mut self, account: Pubkey, role: Role) -> Result<()> {
+        let position = self.roles.iter().position(|grant| grant.account == account && grant.role == role)
+            .ok_or(crate::errors::SolFlexError::RoleNotGranted)?;
+
+        self.roles.remove(position);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    /// The authority holds every role implicitly only while no multisig is configured;
+    /// with one, only explicit grants count.
+    pub fn has_role(&self, account: Pubkey, role: Role) -> bool {
+        (self.threshold == 0 && account == self.authority) || self.roles.contains(&RoleGrant { account, role })
+    }
+
+    pub fn require_not_paused(&self, scope: PauseScope) -> Result<()> {
+        require!(!self.pause_flags.is_paused(scope), crate::errors::SolFlexError::ProgramPaused);
+        Ok(())
+    }
+
+    /// The guardian may add flags without a proposal but never clear them.
+    pub fn is_guardian_pause(&self, signer: Pubkey, flags: &PauseFlags) -> bool {
+        self.guardian == Some(signer) && flags.contains(&self.pause_flags)
+    }
+
+    pub fn set_pause(&mut self, flags: PauseFlags) -> Result<()> {
+        self.pause_flags = flags;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn update_config(&mut self, min_reflection_pool: u64, min_reflection_per_account: u64) -> Result<()> {
         self.min_reflection_pool = min_reflection_pool;
         self.min_reflection_per_account = min_reflection_per_account;
         self.updated_at = Clock::get().unwrap().unix_timestamp;
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn propose_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(new_authority != self.authority, crate::errors::SolFlexError::InvalidParameters);
+
+        self.pending_authority = Some(new_authority);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    /// Completes a transfer; `signer` must be the proposed authority.
+    pub fn accept_authority(&mut self, signer: Pubkey) -> Result<()> {
+        let pending = self.pending_authority.ok_or(crate::errors::SolFlexError::NoPendingAuthority)?;
+        require!(signer == pending, crate::errors::SolFlexError::Unauthorized);
+
+        self.authority = pending;
+        self.pending_authority = None;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(&mut self) -> Result<()> {
+        require!(self.pending_authority.is_some(), crate::errors::SolFlexError::NoPendingAuthority);
+
+        self.pending_authority = None;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn is_multisig_signer(&self, account: Pubkey) -> bool {
+        self.threshold > 0 && self.signers.contains(&account)
+    }
+
+    /// Authorizes an admin action. Without a multisig the signer must hold the action's
+    /// role (the authority for SetMultisig); with one, `proposal` must be approved for
+    /// exactly this action, these `targets` (the accounts the action writes) and params,
+    /// and any signer may submit it.
+    pub fn authorize_admin<T: AnchorSerialize>(
+        &self,
+        signer: Pubkey,
+        proposal: Option<&mut Proposal>,
+        action: AdminAction,
+        targets: &[Pubkey],
+        params: &T,
+    ) -> Result<()> {
+        if self.threshold == 0 {
+            let authorized = match action.role() {
+                Some(role) => self.has_role(signer, role),
+                None => signer == self.authority,
+            };
+            require!(authorized, crate::errors::SolFlexError::Unauthorized);
+            return Ok(());
+        }
+        let proposal = proposal.ok_or(crate::errors::SolFlexError::ProposalRequired)?;
+        proposal.execute(self, action, targets, params)
+    }
+
+    /// Replaces the signer set and lapses every open proposal. A threshold of 0 with no
+    /// signers returns control to the authority.
+    pub fn set_multisig(&mut self, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(signers.len() <= crate::constants::MAX_MULTISIG_SIGNERS, crate::errors::SolFlexError::InvalidMultisig);
+        require!(
+            (threshold == 0 && signers.is_empty()) || (threshold > 0 && threshold as usize <= signers.len()),
+            crate::errors::SolFlexError::InvalidMultisig
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), crate::errors::SolFlexError::InvalidMultisig);
+        }
+
+        self.signers = signers;
+        self.threshold = threshold;
+        self.multisig_nonce = self.multisig_nonce
+            .checked_add(1)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+}
@@ -1,4 +1,77 @@
 use anchor_lang::prelude::*;
+use crate::state::{HolderRewards, UserPreferences};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum DistributionMode {
+    Equal, // Every processed recipient receives the same amount
+    Proportional, // Recipients are paid pro-rata to their token balance
+    Accumulator, // Reward-per-share accounting over staked tokens; holders pull rewards with `claim`
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum CyclePhase {
+    Idle, // No cycle is running; the next reflect opens one
+    Snapshot, // Batches record each holder's balance on its UserPreferences; nothing is paid
+    Payout, // Batches pay holders against the balance recorded in the snapshot sweep
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum CrankReward {
+    None,
+    BasisPoints(u16), // Fraction of each distributed batch
+    Flat(u64), // Fixed amount per successful batch; Cranker-role distributions only
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum DustPolicy {
+    Hold, // Dust accumulates in dust_pool until the policy changes
+    RollOver(u64), // Dust moves back into reflection_pool once dust_pool reaches this amount
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ReflectThrottle {
+    BasisPoints(u16), // Fraction of reflection_pool budgeted per cycle
+    Fixed(u64), // Fixed amount per cycle, capped at reflection_pool
+    DrainAll, // Whole reflection_pool per cycle
+}
+
+impl ReflectThrottle {
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            ReflectThrottle::BasisPoints(bps) => {
+                require!(bps > 0 && bps <= 10000, crate::errors::SolFlexError::InvalidParameters);
+            }
+            ReflectThrottle::Fixed(amount) => {
+                require!(amount > 0, crate::errors::SolFlexError::InvalidParameters);
+            }
+            ReflectThrottle::DrainAll => {}
+        }
+        Ok(())
+    }
+
+    /// Amount of `pool` one cycle may spend. `Fixed` is in units of whichever asset the pool holds.
+    pub fn budget(&self, pool: u64) -> Result<u64> {
+        let budget = match *self {
+            ReflectThrottle::BasisPoints(bps) => {
+                pool
+                    .checked_mul(bps as u64)
+                    .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                    / 10000
+            }
+            ReflectThrottle::Fixed(amount) => amount.min(pool),
+            ReflectThrottle::DrainAll => pool,
+        };
+        Ok(budget)
+    }
+}
+
+// Amounts credited to each bucket by one fee recording.
+pub struct FeeSplit {
+    pub reflection: u64,
+    pub burn: u64,
+    pub project: u64,
+    pub dev: u64,
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -7,20 +80,89 @@ pub struct DistributionConfig {
     pub fee_vault: Pubkey, // Program-owned token vault that receives fee deposits
     pub reflection_pool: u64, // Accounting mirror for reflection amount held in fee_vault
     pub dev_pool: u64, // Accounting mirror for dev amount held in fee_vault
+    pub burn_pool: u64, // Accounting mirror for amount awaiting burn in fee_vault
+    pub project_pool: u64, // Accounting mirror for project amount held in fee_vault
+    pub total_burned: u64, // Total amount burned from fee_vault
     pub total_fees: u64, // Total recorded incoming fees
-    pub last_paid: Pubkey, // Cursor: last user paid in ordered traversal
+    pub holder_cursor: u64, // Next UserPreferences.holder_index the running sweep expects
+    pub cycle_phase: CyclePhase, // Which sweep of the current reflect cycle is running
     pub limit: u32, // Max users to process per reflect call
+    pub distribution_mode: DistributionMode, // How a reflect batch is split across recipients
+    pub throttle: ReflectThrottle, // How much of reflection_pool each cycle may spend
     pub reflection_rate: u16, // Reflection fee rate (basis points)
     pub burn_rate: u16, // Burn fee rate (basis points)
     pub project_rate: u16, // Project fee rate (basis points)
     pub project_account: Pubkey, // Account to receive project fees
     pub dev_fee_rate: u16, // Dev fee rate (0.2% = 20 basis points)
     pub dev_account: Pubkey, // Account to receive dev fees
+    pub acc_reward_per_share: u128, // Accumulated reflection per share, scaled by ACC_REWARD_PRECISION
+    pub total_shares: u64, // Sum of HolderRewards shares, backed by tokens in the stake vault
+    pub unallocated_rewards: u64, // Reflection recorded while total_shares was zero
+    pub accrued_liability: u64, // Accumulator rewards owed to stakers, unallocated_rewards included; never spendable
+    pub claims_pool: u64, // Accounting mirror for amounts reserved by published merkle epochs
+    pub current_epoch: u64, // Last published merkle epoch
+    pub permissionless_crank: bool, // Allow any signer to crank reflect
+    pub crank_reward: CrankReward, // Paid from reflection_pool to non-authority cranks
+    pub max_crank_reward_bps: u16, // Cap on crank rewards per cycle, in basis points of cycle_budget
+    pub last_reflect_at: i64, // Timestamp of the last reflect that paid or reset the cursor
+    pub cycle_id: u64, // Current reflect cycle; advanced when a snapshot sweep opens it
+    pub cycle_budget: u64, // Amount snapshotted for the current cycle
+    pub cycle_rate: u128, // Per-holder amount (Equal) or per-token rate scaled by ACC_REWARD_PRECISION (Proportional)
+    pub cycle_distributed: u64, // Amount paid so far in the current cycle
+    pub cycle_recipients: u32, // Recipients processed so far in the current cycle
+    pub cycle_weight: u64, // Sum of recipient balances processed so far in the current cycle
+    pub last_cycle_recipients: u32, // Recipients processed in the previous cycle
+    pub last_cycle_weight: u64, // Sum of recipient balances processed in the previous cycle
+    pub cycle_holders: u64, // Config.holder_count when the cycle opened; both sweeps end at this holder_index
+    pub cycle_eligible: u64, // Holders the snapshot sweep recorded with a non-zero balance; divides Equal budgets
+    pub cycle_crank_rewards: u64, // Crank rewards paid so far in the current cycle
+    pub halted: bool, // Set by reconcile when fee_vault holds less than the tracked pools
+    pub dust_pool: u64, // Accounting mirror for rounding remainders held in fee_vault
+    pub dust_policy: DustPolicy, // When dust is rolled back into reflection_pool
+    pub cycle_dust_fraction: u128, // Sub-unit Proportional rounding carried within a cycle, scaled by ACC_REWARD_PRECISION
+    pub reward_asset_count: u8, // Extra reward mints registered as RewardAsset PDAs
+    pub auto_create_accounts: bool, // Let reflect create missing recipient ATAs from the rent reserve
+    pub max_rent_per_batch: u64, // Lamports the rent reserve may spend per reflect call
+    pub pending_pool: u64, // Accounting mirror for reflections owed to unpayable recipients (claim_pending)
     pub created_at: i64,
     pub updated_at: i64,
     pub bump: u8, // Store PDA bump seed for efficiency
 }
 
+/// Layout of the singleton `[DISTRIBUTION_CONFIG_SEED]` account written before distributions
+/// were seeded by mint. Only read by `migrate_legacy_distribution`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyDistributionConfig {
+    pub token_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub reflection_pool: u64,
+    pub dev_pool: u64,
+    pub total_fees: u64,
+    pub last_paid: Pubkey,
+    pub limit: u32,
+    pub reflection_rate: u16,
+    pub burn_rate: u16,
+    pub project_rate: u16,
+    pub project_account: Pubkey,
+    pub dev_fee_rate: u16,
+    pub dev_account: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyDistributionConfig {
+    /// Reads a legacy account; it shares the `DistributionConfig` discriminator.
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == <DistributionConfig as anchor_lang::Discriminator>::DISCRIMINATOR,
+            crate::errors::SolFlexError::InvalidConfig
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| crate::errors::SolFlexError::InvalidConfig.into())
+    }
+}
+
 impl DistributionConfig {
     pub const SEED_PREFIX: &'static [u8] = crate::constants::DISTRIBUTION_CONFIG_SEED;
 
@@ -37,15 +179,50 @@ impl DistributionConfig {
             fee_vault,
             reflection_pool: 0,
             dev_pool: 0,
+            burn_pool: 0,
+            project_pool: 0,
+            total_burned: 0,
             total_fees: 0,
-            last_paid: Pubkey::default(),
+            holder_cursor: 0,
+            cycle_phase: CyclePhase::Idle,
             limit: 100,
+            distribution_mode: DistributionMode::Equal,
+            throttle: ReflectThrottle::BasisPoints(1000), // 10%
             reflection_rate: 1000, // 10%
             burn_rate: 200, // 2%
             project_rate: 200, // 2%
             project_account,
             dev_fee_rate: 20, // 0.2%
             dev_account,
+            acc_reward_per_share: 0,
+            total_shares: 0,
+            unallocated_rewards: 0,
+            accrued_liability: 0,
+            claims_pool: 0,
+            current_epoch: 0,
+            permissionless_crank: false,
+            crank_reward: CrankReward::None,
+            max_crank_reward_bps: 0,
+            last_reflect_at: 0,
+            cycle_id: 0,
+            cycle_budget: 0,
+            cycle_rate: 0,
+            cycle_distributed: 0,
+            cycle_recipients: 0,
+            cycle_weight: 0,
+            last_cycle_recipients: 0,
+            last_cycle_weight: 0,
+            cycle_holders: 0,
+            cycle_eligible: 0,
+            cycle_crank_rewards: 0,
+            halted: false,
+            dust_pool: 0,
+            dust_policy: DustPolicy::RollOver(0),
+            cycle_dust_fraction: 0,
+            reward_asset_count: 0,
+            auto_create_accounts: false,
+            max_rent_per_batch: 0,
+            pending_pool: 0,
             created_at: clock.unix_timestamp,
             updated_at: clock.unix_timestamp,
             bump,
@@ -58,22 +235,433 @@ impl DistributionConfig {
         require!(self.project_rate <= 10000, crate::errors::SolFlexError::InvalidParameters);
         require!(self.dev_fee_rate <= 10000, crate::errors::SolFlexError::InvalidParameters);
 
+        self.throttle.validate()?;
+
+        if let CrankReward::BasisPoints(bps) = self.crank_reward {
+            require!(bps <= 10000, crate::errors::SolFlexError::InvalidParameters);
+        }
+        require!(self.max_crank_reward_bps <= 10000, crate::errors::SolFlexError::InvalidParameters);
+        if self.permissionless_crank {
+            require!(self.permits_permissionless_crank(), crate::errors::SolFlexError::InvalidParameters);
+        }
+
         let total_rate = self.reflection_rate + self.burn_rate + self.project_rate + self.dev_fee_rate;
         require!(total_rate <= 10000, crate::errors::SolFlexError::InvalidParameters);
 
         Ok(())
     }
 
-    pub fn validate_reflection_dev_only_split(&self) -> Result<()> {
-        require!(self.burn_rate == 0, crate::errors::SolFlexError::InvalidConfig);
-        require!(self.project_rate == 0, crate::errors::SolFlexError::InvalidConfig);
+    pub fn validate_record_split(&self) -> Result<()> {
         require!(
-            self.reflection_rate + self.dev_fee_rate == 10000,
+            self.reflection_rate + self.burn_rate + self.project_rate + self.dev_fee_rate == 10000,
             crate::errors::SolFlexError::InvalidConfig
         );
         Ok(())
     }
 
+    /// Splits `amount` of incoming fees across the reflection, burn, project and dev pools.
+    pub fn record_fees(&mut self, amount: u64) -> Result<FeeSplit> {
+        self.validate_record_split()?;
+
+        let share = |rate: u16| -> Result<u64> {
+            Ok(amount
+                .checked_mul(rate as u64)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                / 10000)
+        };
+        let split = FeeSplit {
+            reflection: share(self.reflection_rate)?,
+            burn: share(self.burn_rate)?,
+            project: share(self.project_rate)?,
+            dev: share(self.dev_fee_rate)?,
+        };
+
+        self.reflection_pool = self
+            .reflection_pool
+            .checked_add(split.reflection)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.burn_pool = self
+            .burn_pool
+            .checked_add(split.burn)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.project_pool = self
+            .project_pool
+            .checked_add(split.project)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.dev_pool = self
+            .dev_pool
+            .checked_add(split.dev)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        // Whatever the floored shares leave behind is tracked as dust.
+        let dust = [split.reflection, split.burn, split.project, split.dev]
+            .iter()
+            .fold(amount, |rest, share| rest - share);
+        self.add_dust(dust)?;
+        if self.distribution_mode == DistributionMode::Accumulator {
+            self.accrue_rewards(split.reflection)?;
+            self.roll_dust()?;
+        }
+        self.total_fees = self
+            .total_fees
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(split)
+    }
+
+    /// Sum of every accounting mirror that must be backed by fee_vault.
+    pub fn tracked_total(&self) -> Result<u64> {
+        [
+            self.dev_pool,
+            self.burn_pool,
+            self.project_pool,
+            self.claims_pool,
+            self.dust_pool,
+            self.pending_pool,
+            self.accrued_liability,
+        ]
+            .iter()
+            .try_fold(self.reflection_pool, |acc, amount| acc.checked_add(*amount))
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow.into())
+    }
+
+    /// Credits an unrecorded vault surplus to the reflection pool.
+    pub fn credit_reflection(&mut self, amount: u64) -> Result<()> {
+        self.reflection_pool = self
+            .reflection_pool
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        if self.distribution_mode == DistributionMode::Accumulator {
+            self.accrue_rewards(amount)?;
+        }
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    pub fn add_dust(&mut self, amount: u64) -> Result<()> {
+        self.dust_pool = self
+            .dust_pool
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Moves a rounding remainder already counted in reflection_pool into dust_pool.
+    pub fn reflection_to_dust(&mut self, amount: u64) -> Result<()> {
+        require!(self.reflection_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.reflection_pool -= amount;
+        self.add_dust(amount)
+    }
+
+    /// Rolls dust_pool back into reflection_pool when the dust policy allows it.
+    pub fn roll_dust(&mut self) -> Result<u64> {
+        let rolled = match self.dust_policy {
+            DustPolicy::RollOver(threshold) if self.dust_pool > 0 && self.dust_pool >= threshold => self.dust_pool,
+            _ => return Ok(0),
+        };
+        self.dust_pool = 0;
+        self.credit_reflection(rolled)?;
+        Ok(rolled)
+    }
+
+    /// Amount of the reflection pool the throttle allows a new cycle to spend.
+    pub fn throttled_budget(&self) -> Result<u64> {
+        self.throttle.budget(self.reflection_pool)
+    }
+
+    /// Per-token rate for a Proportional cycle paying `budget` out of `mint_supply`.
+    /// Paid balances never sum past the supply, so the rate never outruns the budget.
+    pub fn proportional_rate(budget: u64, mint_supply: u64) -> Result<u128> {
+        require!(mint_supply > 0, crate::errors::SolFlexError::NoEligibleAccounts);
+        Ok((budget as u128)
+            .checked_mul(crate::constants::ACC_REWARD_PRECISION)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / mint_supply as u128)
+    }
+
+    /// Per-holder amount for an Equal cycle paying `budget` to `holders`.
+    pub fn equal_rate(budget: u64, holders: u64) -> Result<u64> {
+        require!(holders > 0, crate::errors::SolFlexError::NoEligibleAccounts);
+        Ok(budget / holders)
+    }
+
+    /// Opens a new reflect cycle with its snapshot sweep over the `registered_holders`
+    /// holders registered so far; later registrations wait for the next cycle.
+    pub fn open_cycle(&mut self, registered_holders: u64) -> Result<()> {
+        require!(registered_holders > 0, crate::errors::SolFlexError::NoEligibleAccounts);
+        self.cycle_id = self
+            .cycle_id
+            .checked_add(1)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.cycle_holders = registered_holders;
+        self.cycle_eligible = 0;
+        self.holder_cursor = 0;
+        self.cycle_phase = CyclePhase::Snapshot;
+        Ok(())
+    }
+
+    /// Moves the sweep cursor past `holder_index`, which must be the next holder in order,
+    /// so a batch cannot skip anyone.
+    pub fn advance_cursor(&mut self, holder_index: u64) -> Result<()> {
+        require!(
+            holder_index == self.holder_cursor && holder_index < self.cycle_holders,
+            crate::errors::SolFlexError::HolderOutOfOrder
+        );
+        self.holder_cursor += 1;
+        Ok(())
+    }
+
+    /// Counts a holder the snapshot sweep recorded, or releases one that was banned before
+    /// the payout sweep reached it.
+    pub fn count_eligible(&mut self, counted: bool) -> Result<()> {
+        self.cycle_eligible = if counted {
+            self.cycle_eligible
+                .checked_add(1)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+        } else {
+            self.cycle_eligible.saturating_sub(1)
+        };
+        Ok(())
+    }
+
+    /// Drops `user_preferences` from the running cycle of this distribution (`distribution`).
+    /// A holder dropped during the snapshot sweep no longer divides the Equal budget; one
+    /// dropped later is not paid and its share stays in reflection_pool.
+    pub fn release_holder(&mut self, distribution: Pubkey, user_preferences: &mut UserPreferences) -> Result<()> {
+        if self.cycle_phase == CyclePhase::Idle || !user_preferences.take_snapshot(distribution, self.cycle_id) {
+            return Ok(());
+        }
+        if self.cycle_phase == CyclePhase::Snapshot {
+            self.count_eligible(false)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the running sweep has visited every holder of the cycle.
+    pub fn sweep_complete(&self) -> bool {
+        self.holder_cursor >= self.cycle_holders
+    }
+
+    /// Ends the snapshot sweep: snapshots the budget and per-holder rate and restarts the
+    /// cursor for the payout sweep. Equal cycles divide the budget by `holder_count`, the
+    /// holders the sweep counted, so none of them is left without budget; Proportional
+    /// cycles divide it by `mint_supply`.
+    pub fn start_cycle(&mut self, budget: u64, mint_supply: u64, holder_count: u64) -> Result<()> {
+        self.cycle_budget = budget;
+        self.cycle_distributed = 0;
+        self.cycle_recipients = 0;
+        self.cycle_weight = 0;
+        self.cycle_dust_fraction = 0;
+        self.cycle_crank_rewards = 0;
+        self.holder_cursor = 0;
+        self.cycle_phase = CyclePhase::Payout;
+        self.cycle_rate = match self.distribution_mode {
+            DistributionMode::Proportional => Self::proportional_rate(budget, mint_supply)?,
+            _ => {
+                let rate = Self::equal_rate(budget, holder_count)?;
+                // The part of the budget that does not divide evenly across holders is dust.
+                let remainder = budget - rate * holder_count;
+                self.reflection_to_dust(remainder)?;
+                self.cycle_budget = budget - remainder;
+                rate as u128
+            }
+        };
+        Ok(())
+    }
+
+    /// Closes the current cycle, keeping its totals for reporting.
+    pub fn end_cycle(&mut self) {
+        self.last_cycle_recipients = self.cycle_recipients;
+        self.last_cycle_weight = self.cycle_weight;
+        self.holder_cursor = 0;
+        self.cycle_phase = CyclePhase::Idle;
+    }
+
+    /// Cycle-rate payout for a recipient holding `balance`; zero once it no longer fits
+    /// in the unspent budget, so paid holders never receive a partial amount.
+    pub fn cycle_payout(&self, balance: u64, already_allocated: u64) -> Result<u64> {
+        let payout = match self.distribution_mode {
+            DistributionMode::Proportional => (balance as u128)
+                .checked_mul(self.cycle_rate)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                / crate::constants::ACC_REWARD_PRECISION,
+            _ => self.cycle_rate,
+        };
+        let remaining = self
+            .cycle_budget
+            .saturating_sub(self.cycle_distributed)
+            .saturating_sub(already_allocated);
+        match u64::try_from(payout) {
+            Ok(payout) if payout <= remaining => Ok(payout),
+            _ => Ok(0),
+        }
+    }
+
+    /// Fraction of a Proportional payout lost to flooring, scaled by ACC_REWARD_PRECISION.
+    pub fn payout_dust_fraction(&self, balance: u64) -> Result<u128> {
+        match self.distribution_mode {
+            DistributionMode::Proportional => Ok((balance as u128)
+                .checked_mul(self.cycle_rate)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                % crate::constants::ACC_REWARD_PRECISION),
+            _ => Ok(0),
+        }
+    }
+
+    /// Records a batch of cycle payouts and the balances they were paid against.
+    pub fn record_cycle_batch(&mut self, distributed: u64, recipients: u32, weight: u64, dust_fraction: u128) -> Result<()> {
+        self.cycle_distributed = self
+            .cycle_distributed
+            .checked_add(distributed)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.cycle_recipients = self
+            .cycle_recipients
+            .checked_add(recipients)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.cycle_weight = self
+            .cycle_weight
+            .checked_add(weight)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+        // Floored fractions add up to whole units of dust over a cycle.
+        let fraction = self
+            .cycle_dust_fraction
+            .checked_add(dust_fraction)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        let whole = u64::try_from(fraction / crate::constants::ACC_REWARD_PRECISION)
+            .map_err(|_| crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.cycle_dust_fraction = fraction % crate::constants::ACC_REWARD_PRECISION;
+        self.reflection_to_dust(whole.min(self.reflection_pool))
+    }
+
+    /// Moves `amount` from reflection_pool into the accrued liability and spreads it over
+    /// the current shares, carrying it until shares exist; it then only leaves through `claim`.
+    pub fn accrue_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(self.reflection_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.reflection_pool -= amount;
+        self.accrued_liability = self
+            .accrued_liability
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+
+        let pending = self
+            .unallocated_rewards
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        if self.total_shares == 0 {
+            self.unallocated_rewards = pending;
+            return Ok(());
+        }
+
+        let increment = (pending as u128)
+            .checked_mul(crate::constants::ACC_REWARD_PRECISION)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / self.total_shares as u128;
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(increment)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        // Keep the part the floored increment cannot represent for the next accrual.
+        let allocated = increment
+            .checked_mul(self.total_shares as u128)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / crate::constants::ACC_REWARD_PRECISION;
+        self.unallocated_rewards = pending - allocated as u64;
+        Ok(())
+    }
+
+    /// Pays settled accumulator rewards out of the accrued liability.
+    pub fn pay_accrued(&mut self, amount: u64) -> Result<()> {
+        require!(self.accrued_liability >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.accrued_liability -= amount;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Settles `holder` and re-checkpoints it at `shares`, keeping total_shares in step.
+    pub fn checkpoint_shares(&mut self, holder: &mut HolderRewards, shares: u64) -> Result<()> {
+        holder.settle_rewards(self.acc_reward_per_share)?;
+        self.total_shares = self
+            .total_shares
+            .checked_sub(holder.shares)
+            .and_then(|total| total.checked_add(shares))
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        holder.set_shares(shares, self.acc_reward_per_share)
+    }
+
+    /// Switches the distribution mode. Leaving Accumulator mode is only allowed once
+    /// every stake is withdrawn and every settled reward is paid; rewards still waiting
+    /// for shares then return to reflection_pool.
+    pub fn set_distribution_mode(&mut self, mode: DistributionMode) -> Result<()> {
+        if mode == self.distribution_mode {
+            return Ok(());
+        }
+        require!(
+            self.total_shares == 0 && self.accrued_liability == self.unallocated_rewards,
+            crate::errors::SolFlexError::SharesOutstanding
+        );
+        self.reflection_pool = self
+            .reflection_pool
+            .checked_add(self.unallocated_rewards)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.accrued_liability = 0;
+        self.unallocated_rewards = 0;
+        self.distribution_mode = mode;
+        Ok(())
+    }
+
+    /// Signers without the Cranker role choose which holders a batch covers. That is only
+    /// safe when a batch pays by balance (sybil wallets weigh nothing) and the reward
+    /// scales with what was paid, so Equal mode and Flat rewards need a trusted crank.
+    pub fn permits_permissionless_crank(&self) -> bool {
+        self.distribution_mode == DistributionMode::Proportional && !matches!(self.crank_reward, CrankReward::Flat(_))
+    }
+
+    /// Crank reward owed for a batch that distributed `distributed_total`, capped at
+    /// what is left of the cycle's `max_crank_reward_bps` allowance and of the pool.
+    pub fn crank_reward_amount(&self, distributed_total: u64) -> Result<u64> {
+        let reward = match self.crank_reward {
+            CrankReward::None => 0,
+            CrankReward::BasisPoints(bps) => {
+                distributed_total
+                    .checked_mul(bps as u64)
+                    .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                    / 10000
+            }
+            CrankReward::Flat(amount) => amount,
+        };
+        let cycle_cap = (self.cycle_budget as u128 * self.max_crank_reward_bps as u128 / 10000) as u64;
+        let cycle_remaining = cycle_cap.saturating_sub(self.cycle_crank_rewards);
+        Ok(reward.min(cycle_remaining).min(self.reflection_pool))
+    }
+
+    pub fn pay_crank_reward(&mut self, amount: u64) -> Result<()> {
+        self.distribute_reflection(amount)?;
+        self.cycle_crank_rewards = self
+            .cycle_crank_rewards
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Moves a recipient's share from the reflection pool into the pending pool.
+    pub fn accrue_pending(&mut self, amount: u64) -> Result<()> {
+        require!(self.reflection_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.reflection_pool -= amount;
+        self.pending_pool = self
+            .pending_pool
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn distribute_pending(&mut self, amount: u64) -> Result<()> {
+        require!(self.pending_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.pending_pool -= amount;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
     pub fn distribute_reflection(&mut self, amount: u64) -> Result<()> {
         require!(self.reflection_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
         self.reflection_pool -= amount;
@@ -81,6 +669,24 @@ impl DistributionConfig {
         Ok(())
     }
 
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        require!(self.burn_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.burn_pool -= amount;
+        self.total_burned = self
+            .total_burned
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    pub fn distribute_project(&mut self, amount: u64) -> Result<()> {
+        require!(self.project_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.project_pool -= amount;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
     pub fn distribute_dev(&mut self, amount: u64) -> Result<()> {
         require!(self.dev_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
         self.dev_pool -= amount;
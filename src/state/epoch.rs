@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+#[account]
+#[derive(InitSpace)]
+pub struct DistributionEpoch {
+    pub epoch: u64, // Sequential epoch number assigned at publish time
+    pub merkle_root: [u8; 32], // Root over keccak(0x00 || index || owner || amount) leaves
+    pub total_amount: u64, // Amount reserved from the reflection pool for this epoch
+    pub claimed_amount: u64, // Amount paid out so far
+    pub num_leaves: u32, // Number of leaves in the snapshot
+    #[max_len(1024)]
+    pub claimed_bitmap: Vec<u8>, // One bit per leaf index
+    pub claim_deadline: i64, // Last timestamp a leaf can be claimed; the rest is reclaimable afterwards
+    pub created_at: i64,
+    pub bump: u8, // Store PDA bump seed for efficiency
+}
+
+impl DistributionEpoch {
+    pub const SEED_PREFIX: &'static [u8] = crate::constants::DISTRIBUTION_EPOCH_SEED;
+
+    pub fn new(epoch: u64, merkle_root: [u8; 32], total_amount: u64, num_leaves: u32, claim_deadline: i64, bump: u8) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            epoch,
+            merkle_root,
+            total_amount,
+            claimed_amount: 0,
+            num_leaves,
+            claimed_bitmap: vec![0u8; (num_leaves as usize).div_ceil(8)],
+            claim_deadline,
+            created_at: clock.unix_timestamp,
+            bump,
+        }
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap
+            .get((index / 8) as usize)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    pub fn set_claimed(&mut self, index: u32) -> Result<()> {
+        require!(index < self.num_leaves, crate::errors::SolFlexError::InvalidParameters);
+        require!(!self.is_claimed(index), crate::errors::SolFlexError::AlreadyClaimed);
+        self.claimed_bitmap[(index / 8) as usize] |= 1 << (index % 8);
+        Ok(())
+    }
+
+    pub fn is_open(&self, now: i64) -> bool {
+        now <= self.claim_deadline
+    }
+
+    /// Amount left unclaimed once the claim window has closed.
+    pub fn unclaimed_amount(&self, now: i64) -> Result<u64> {
+        require!(!self.is_open(now), crate::errors::SolFlexError::ClaimWindowOpen);
+        Ok(self.total_amount - self.claimed_amount)
+    }
+
+    pub fn leaf(index: u32, owner: &Pubkey, amount: u64) -> [u8; 32] {
+        keccak::hashv(&[&[0u8], &index.to_le_bytes(), owner.as_ref(), &amount.to_le_bytes()]).0
+    }
+
+    /// Verifies a proof built with sorted-pair hashing of `keccak(0x01 || left || right)`.
+    pub fn verify_proof(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            keccak::hashv(&[&[1u8], &left, &right]).0
+        });
+        computed == self.merkle_root
+    }
+}
@@ -1,7 +1,17 @@
 pub mod config;
 pub mod token;
 pub mod distribution;
+pub mod epoch;
+pub mod rewards;
+pub mod reward_asset;
+pub mod proposal;
+pub mod timelock;
 
 pub use config::*;
 pub use token::*;
-pub use distribution::*;
\ No newline at end of file
+pub use distribution::*;
+pub use epoch::*;
+pub use rewards::*;
+pub use reward_asset::*;
+pub use proposal::*;
+pub use timelock::*;
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::state::{Config, Role};
+
+/// Admin instructions that require an approved proposal once a multisig is configured
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum AdminAction {
+    UpdateConfig,
+    SetDistributionConfig,
+    AddPool,
+    RemovePool,
+    AddToBlocklist,
+    RemoveFromBlocklist,
+    SetMultisig,
+    QueueConfigChange,
+    GrantRole,
+    RevokeRole,
+    SetGuardian,
+    SetPause,
+    ProposeAuthority,
+    CancelAuthorityTransfer,
+    CancelConfigChange,
+}
+
+impl AdminAction {
+    /// Role that may perform the action directly while no multisig is configured.
+    pub fn role(&self) -> Option<Role> {
+        match self {
+            AdminAction::UpdateConfig | AdminAction::SetDistributionConfig => Some(Role::Admin),
+            AdminAction::AddPool | AdminAction::RemovePool => Some(Role::PoolManager),
+            AdminAction::AddToBlocklist | AdminAction::RemoveFromBlocklist => Some(Role::BlocklistManager),
+            AdminAction::QueueConfigChange | AdminAction::CancelConfigChange => Some(Role::Admin),
+            AdminAction::SetMultisig
+            | AdminAction::GrantRole
+            | AdminAction::RevokeRole
+            | AdminAction::SetGuardian
+            | AdminAction::SetPause
+            | AdminAction::ProposeAuthority
+            | AdminAction::CancelAuthorityTransfer => None,
+        }
+    }
+}
+
+// Pending admin action collecting approvals from the Config signer set
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64, // Config.proposal_count at creation
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub params_hash: [u8; 32], // sha256 of the Borsh-serialized target accounts and instruction params
+    #[max_len(10)]
+    pub approvals: Vec<Pubkey>, // Signers that approved, proposer included
+    pub executed: bool,
+    pub multisig_nonce: u64, // Config.multisig_nonce at creation; set_multisig invalidates older proposals
+    pub created_at: i64,
+    pub expires_at: i64, // created_at + PROPOSAL_LIFETIME
+    pub updated_at: i64,
+    pub bump: u8, // Store PDA bump seed for efficiency
+}
+
+impl Proposal {
+    pub const SEED_PREFIX: &'static [u8] = crate::constants::PROPOSAL_SEED;
+
+    pub fn new(
+        id: u64,
+        proposer: Pubkey,
+        action: AdminAction,
+        params_hash: [u8; 32],
+        multisig_nonce: u64,
+        bump: u8,
+    ) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            id,
+            proposer,
+            action,
+            params_hash,
+            approvals: vec![proposer],
+            executed: false,
+            multisig_nonce,
+            created_at: clock.unix_timestamp,
+            expires_at: clock.unix_timestamp.saturating_add(crate::constants::PROPOSAL_LIFETIME),
+            updated_at: clock.unix_timestamp,
+            bump,
+        }
+    }
+
+    /// sha256 of the Borsh-serialized `targets` (as a Vec) followed by `params`, so an
+    /// approval cannot be replayed against other accounts with the same params.
+    pub fn hash_params<T: AnchorSerialize>(targets: &[Pubkey], params: &T) -> Result<[u8; 32]> {
+        Ok(hashv(&[&targets.to_vec().try_to_vec()?, &params.try_to_vec()?]).to_bytes())
+    }
+
+    /// True once the proposal can no longer be executed: executed, expired, or created
+    /// under a signer set that set_multisig has since replaced.
+    pub fn is_closed(&self, config: &Config) -> bool {
+        self.executed
+            || self.multisig_nonce != config.multisig_nonce
+            || Clock::get().unwrap().unix_timestamp >= self.expires_at
+    }
+
+    fn require_open(&self, config: &Config) -> Result<()> {
+        require!(!self.executed, crate::errors::SolFlexError::ProposalAlreadyExecuted);
+        require!(self.multisig_nonce == config.multisig_nonce, crate::errors::SolFlexError::ProposalStale);
+        require!(
+            Clock::get()?.unix_timestamp < self.expires_at,
+            crate::errors::SolFlexError::ProposalExpired
+        );
+        Ok(())
+    }
+
+    pub fn approve(&mut self, config: &Config, signer: Pubkey) -> Result<()> {
+        self.require_open(config)?;
+        require!(!self.approvals.contains(&signer), crate::errors::SolFlexError::AlreadyApproved);
+
+        self.approvals.push(signer);
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+
+    /// Only approvals from the current signer set count, so removing a signer
+    /// also withdraws their approvals on open proposals.
+    pub fn approval_count(&self, config: &Config) -> usize {
+        self.approvals.iter().filter(|signer| config.is_multisig_signer(**signer)).count()
+    }
+
+    /// Marks the proposal executed if it matches `action`, `targets` and `params` and has
+    /// reached the threshold.
+    pub fn execute<T: AnchorSerialize>(
+        &mut self,
+        config: &Config,
+        action: AdminAction,
+        targets: &[Pubkey],
+        params: &T,
+    ) -> Result<()> {
+        self.require_open(config)?;
+        require!(
+            self.action == action && self.params_hash == Self::hash_params(targets, params)?,
+            crate::errors::SolFlexError::ProposalMismatch
+        );
+        require!(
+            self.approval_count(config) >= config.threshold as usize,
+            crate::errors::SolFlexError::ThresholdNotMet
+        );
+
+        self.executed = true;
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::state::{DistributionConfig, DistributionMode, ReflectThrottle};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RewardAssetKind {
+    Token, // SPL Token / Token-2022 mint held in `vault`
+    NativeSol, // Lamports held by the RewardAsset PDA itself, paid straight to wallets
+}
+
+// Extra reward mint paid alongside the base token by a distribution's reflect cycles
+#[account]
+#[derive(InitSpace)]
+pub struct RewardAsset {
+    pub distribution: Pubkey, // DistributionConfig whose holder set and cursor this asset follows
+    pub kind: RewardAssetKind,
+    pub reward_mint: Pubkey, // Native mint for NativeSol
+    pub vault: Pubkey, // Token account owned by the distribution_config PDA; this PDA for NativeSol
+    pub token_program: Pubkey, // Token program owning reward_mint; system program for NativeSol
+    pub reflection_pool: u64, // Accounting mirror for the amount held in vault awaiting reflection
+    pub total_deposited: u64, // Total amount credited through deposit_reward / deposit_sol_reward
+    pub min_payout: u64, // Per-recipient minimum (net of transfer fees) in reward_mint units
+    pub throttle: ReflectThrottle, // Per-cycle budget of this asset's reflection_pool; Fixed is in reward_mint units
+    pub cycle_id: u64, // Distribution cycle this asset's snapshot belongs to
+    pub cycle_budget: u64, // Amount snapshotted for the current cycle
+    pub cycle_rate: u128, // Per-holder amount (Equal) or per-token rate scaled by ACC_REWARD_PRECISION (Proportional)
+    pub cycle_distributed: u64, // Amount paid so far in the current cycle
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8, // Store PDA bump seed for efficiency
+}
+
+impl RewardAsset {
+    pub const SEED_PREFIX: &'static [u8] = crate::constants::REWARD_ASSET_SEED;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        distribution: Pubkey,
+        kind: RewardAssetKind,
+        reward_mint: Pubkey,
+        vault: Pubkey,
+        token_program: Pubkey,
+        min_payout: u64,
+        throttle: ReflectThrottle,
+        bump: u8,
+    ) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            distribution,
+            kind,
+            reward_mint,
+            vault,
+            token_program,
+            reflection_pool: 0,
+            total_deposited: 0,
+            min_payout,
+            throttle,
+            cycle_id: 0,
+            cycle_budget: 0,
+            cycle_rate: 0,
+            cycle_distributed: 0,
+            created_at: clock.unix_timestamp,
+            updated_at: clock.unix_timestamp,
+            bump,
+        }
+    }
+
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.reflection_pool = self
+            .reflection_pool
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Snapshots this asset's budget and rate for the distribution's current cycle,
+    /// using this asset's own throttle and the distribution's holder snapshot. A remainder
+    /// that does not divide evenly in Equal mode stays in reflection_pool for the next cycle.
+    pub fn sync_cycle(&mut self, distribution: &DistributionConfig, mint_supply: u64) -> Result<()> {
+        if self.cycle_id == distribution.cycle_id {
+            return Ok(());
+        }
+        let budget = self.throttle.budget(self.reflection_pool)?;
+        self.cycle_id = distribution.cycle_id;
+        self.cycle_distributed = 0;
+        match distribution.distribution_mode {
+            DistributionMode::Proportional => {
+                self.cycle_rate = DistributionConfig::proportional_rate(budget, mint_supply)?;
+                self.cycle_budget = budget;
+            }
+            _ => {
+                let holders = distribution.cycle_eligible;
+                let rate = DistributionConfig::equal_rate(budget, holders)?;
+                self.cycle_rate = rate as u128;
+                self.cycle_budget = rate * holders;
+            }
+        }
+        Ok(())
+    }
+
+    /// Same all-or-nothing rule as `DistributionConfig::cycle_payout`.
+    pub fn cycle_payout(&self, mode: DistributionMode, balance: u64, already_allocated: u64) -> Result<u64> {
+        let payout = match mode {
+            DistributionMode::Proportional => (balance as u128)
+                .checked_mul(self.cycle_rate)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+                / crate::constants::ACC_REWARD_PRECISION,
+            _ => self.cycle_rate,
+        };
+        let remaining = self
+            .cycle_budget
+            .saturating_sub(self.cycle_distributed)
+            .saturating_sub(already_allocated);
+        match u64::try_from(payout) {
+            Ok(payout) if payout <= remaining => Ok(payout),
+            _ => Ok(0),
+        }
+    }
+
+    pub fn distribute(&mut self, amount: u64) -> Result<()> {
+        require!(self.reflection_pool >= amount, crate::errors::SolFlexError::InsufficientFunds);
+        self.reflection_pool -= amount;
+        self.cycle_distributed = self
+            .cycle_distributed
+            .checked_add(amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
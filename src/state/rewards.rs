@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct HolderRewards {
+    pub distribution: Pubkey, // DistributionConfig this checkpoint belongs to
+    pub owner: Pubkey,
+    pub staked: u64, // Tokens this owner holds in the distribution's stake vault
+    pub shares: u64, // Earning weight: `staked` while eligible, zero while banned or blocklisted
+    pub reward_debt: u128, // shares * acc_reward_per_share at the last checkpoint
+    pub pending_rewards: u64, // Settled but not yet paid rewards
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8, // Store PDA bump seed for efficiency
+}
+
+impl HolderRewards {
+    pub const SEED_PREFIX: &'static [u8] = crate::constants::HOLDER_REWARDS_SEED;
+
+    pub fn new(distribution: Pubkey, owner: Pubkey, bump: u8) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            distribution,
+            owner,
+            staked: 0,
+            shares: 0,
+            reward_debt: 0,
+            pending_rewards: 0,
+            created_at: clock.unix_timestamp,
+            updated_at: clock.unix_timestamp,
+            bump,
+        }
+    }
+
+    /// Moves rewards accrued since the last checkpoint into `pending_rewards`.
+    pub fn settle_rewards(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let accumulated = (self.shares as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / crate::constants::ACC_REWARD_PRECISION;
+        let accrued = u64::try_from(accumulated.saturating_sub(self.reward_debt))
+            .map_err(|_| crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(accrued)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+
+    /// Re-checkpoints shares; call `settle_rewards` first so nothing accrued is lost.
+    pub fn set_shares(&mut self, shares: u64, acc_reward_per_share: u128) -> Result<()> {
+        self.shares = shares;
+        self.reward_debt = (shares as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / crate::constants::ACC_REWARD_PRECISION;
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, CrankReward, DistributionConfig, DistributionMode, DustPolicy, ReflectThrottle};
+
+/// Timelocked change, applied by execute_config_change once its delay has passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ConfigChange {
+    Rates {
+        reflection_rate: u16,
+        burn_rate: u16,
+        project_rate: u16,
+        dev_fee_rate: u16,
+    },
+    DevAccount(Pubkey),
+    ProjectAccount(Pubkey),
+    FeeVault(Pubkey),
+    Authority(Pubkey), // Proposes the new authority; it must still sign accept_authority
+    TimelockDelay(i64),
+    CrankSettings {
+        permissionless_crank: bool,
+        crank_reward: CrankReward,
+        max_crank_reward_bps: u16,
+    },
+    Throttle(ReflectThrottle),
+    DistributionMode(DistributionMode), // Subject to the Accumulator exit rule of set_distribution_mode
+    DustPolicy(DustPolicy),
+    AccountCreation {
+        auto_create_accounts: bool,
+        max_rent_per_batch: u64,
+    },
+    RemoveRewardAsset(Pubkey), // Reward mint; carried out by remove_reward_asset, not execute_config_change
+}
+
+impl ConfigChange {
+    /// Distribution changes target a DistributionConfig; authority and delay changes target Config.
+    pub fn targets_distribution(&self) -> bool {
+        !matches!(self, ConfigChange::Authority(_) | ConfigChange::TimelockDelay(_))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            ConfigChange::Rates { reflection_rate, burn_rate, project_rate, dev_fee_rate } => {
+                let total_rate = reflection_rate as u32 + burn_rate as u32 + project_rate as u32 + dev_fee_rate as u32;
+                require!(total_rate == 10000, crate::errors::SolFlexError::InvalidParameters);
+            }
+            ConfigChange::TimelockDelay(delay) => {
+                require!(
+                    (0..=crate::constants::MAX_TIMELOCK_DELAY).contains(&delay),
+                    crate::errors::SolFlexError::InvalidParameters
+                );
+            }
+            ConfigChange::DevAccount(key)
+            | ConfigChange::ProjectAccount(key)
+            | ConfigChange::FeeVault(key)
+            | ConfigChange::Authority(key)
+            | ConfigChange::RemoveRewardAsset(key) => {
+                require!(key != Pubkey::default(), crate::errors::SolFlexError::InvalidParameters);
+            }
+            ConfigChange::CrankSettings { crank_reward, max_crank_reward_bps, .. } => {
+                if let CrankReward::BasisPoints(bps) = crank_reward {
+                    require!(bps <= 10000, crate::errors::SolFlexError::InvalidParameters);
+                }
+                require!(max_crank_reward_bps <= 10000, crate::errors::SolFlexError::InvalidParameters);
+            }
+            ConfigChange::Throttle(throttle) => throttle.validate()?,
+            ConfigChange::DistributionMode(_) | ConfigChange::DustPolicy(_) | ConfigChange::AccountCreation { .. } => {}
+        }
+        Ok(())
+    }
+
+    pub fn apply_to_distribution(&self, distribution: &mut DistributionConfig) -> Result<()> {
+        match *self {
+            ConfigChange::Rates { reflection_rate, burn_rate, project_rate, dev_fee_rate } => {
+                distribution.reflection_rate = reflection_rate;
+                distribution.burn_rate = burn_rate;
+                distribution.project_rate = project_rate;
+                distribution.dev_fee_rate = dev_fee_rate;
+                distribution.validate_rates()?;
+            }
+            ConfigChange::DevAccount(key) => distribution.dev_account = key,
+            ConfigChange::ProjectAccount(key) => distribution.project_account = key,
+            ConfigChange::FeeVault(key) => distribution.fee_vault = key,
+            // Settings are checked against the rest of the distribution when they apply.
+            ConfigChange::CrankSettings { permissionless_crank, crank_reward, max_crank_reward_bps } => {
+                distribution.permissionless_crank = permissionless_crank;
+                distribution.crank_reward = crank_reward;
+                distribution.max_crank_reward_bps = max_crank_reward_bps;
+                distribution.validate_rates()?;
+            }
+            ConfigChange::Throttle(throttle) => {
+                distribution.throttle = throttle;
+                distribution.validate_rates()?;
+            }
+            ConfigChange::DistributionMode(mode) => {
+                distribution.set_distribution_mode(mode)?;
+                distribution.validate_rates()?;
+            }
+            ConfigChange::DustPolicy(policy) => distribution.dust_policy = policy,
+            ConfigChange::AccountCreation { auto_create_accounts, max_rent_per_batch } => {
+                distribution.auto_create_accounts = auto_create_accounts;
+                distribution.max_rent_per_batch = max_rent_per_batch;
+            }
+            _ => return err!(crate::errors::SolFlexError::InvalidParameters),
+        }
+        distribution.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    pub fn apply_to_config(&self, config: &mut Config) -> Result<()> {
+        match *self {
+            ConfigChange::Authority(key) => config.propose_authority(key),
+            ConfigChange::TimelockDelay(delay) => {
+                config.timelock_delay = delay;
+                config.updated_at = Clock::get()?.unix_timestamp;
+                Ok(())
+            }
+            _ => err!(crate::errors::SolFlexError::InvalidParameters),
+        }
+    }
+}
+
+// Queued change readable on-chain until it is executed or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigChange {
+    pub id: u64, // Config.change_count at queue time
+    pub target: Pubkey, // DistributionConfig for distribution changes, Config otherwise
+    pub change: ConfigChange,
+    pub queued_by: Pubkey, // Receives the rent back on execute or cancel
+    pub queued_at: i64,
+    pub executable_at: i64, // queued_at + Config.timelock_delay
+    pub bump: u8, // Store PDA bump seed for efficiency
+}
+
+impl PendingConfigChange {
+    pub const SEED_PREFIX: &'static [u8] = crate::constants::PENDING_CHANGE_SEED;
+
+    pub fn new(id: u64, target: Pubkey, change: ConfigChange, queued_by: Pubkey, delay: i64, bump: u8) -> Result<Self> {
+        let now = Clock::get()?.unix_timestamp;
+        Ok(Self {
+            id,
+            target,
+            change,
+            queued_by,
+            queued_at: now,
+            executable_at: now
+                .checked_add(delay)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?,
+            bump,
+        })
+    }
+}
@@ -56,6 +56,21 @@ impl TokenPool {
     }
 }
 
+// Reflection held for a user whose token account could not be paid during reflect
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct PendingReflection {
+    pub distribution: Pubkey, // DistributionConfig the amount is owed from
+    pub amount: u64,
+}
+
+// Balance a distribution's snapshot sweep recorded for a holder
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct CycleSnapshot {
+    pub distribution: Pubkey, // DistributionConfig whose sweep recorded it
+    pub cycle_id: u64,
+    pub balance: u64,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserPreferences {
@@ -66,12 +81,47 @@ pub struct UserPreferences {
     pub is_banned: bool,
     pub created_at: i64,
     pub updated_at: i64,
+    // Fields below were added after the first deployment; keep new ones appended.
+    #[max_len(4)]
+    pub pending_reflections: Vec<PendingReflection>, // Claimable via claim_pending, one entry per distribution
+    pub rent_funded: bool, // A rent reserve has created token accounts for this owner; it never pays again
+    pub holder_index: u64, // Config.holder_count when created; reflect sweeps holders in this order
+    #[max_len(8)]
+    pub cycle_snapshots: Vec<CycleSnapshot>, // Latest snapshot per distribution; reflect pays at most this balance
+}
+
+/// Layout of `UserPreferences` before the fields appended after `updated_at`. Only read by
+/// `migrate_user_preferences`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserPreferences {
+    pub owner: Pubkey,
+    pub preferred_pool_id: u64,
+    pub custom_memo: String,
+    pub is_banned: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl LegacyUserPreferences {
+    /// Reads a `UserPreferences` account that has not been grown to the current layout.
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        require!(
+            !UserPreferences::is_current_layout(info),
+            crate::errors::SolFlexError::AlreadyMigrated
+        );
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == <UserPreferences as anchor_lang::Discriminator>::DISCRIMINATOR,
+            crate::errors::SolFlexError::InvalidConfig
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| crate::errors::SolFlexError::InvalidConfig.into())
+    }
 }
 
 impl UserPreferences {
     pub const SEED_PREFIX: &'static [u8] = crate::constants::USER_PREFERENCES_SEED;
 
-    pub fn new(owner: Pubkey) -> Self {
+    pub fn new(owner: Pubkey, holder_index: u64) -> Self {
         let clock = Clock::get().unwrap();
         Self {
             owner,
@@ -80,6 +130,83 @@ impl UserPreferences {
             is_banned: false,
             created_at: clock.unix_timestamp,
             updated_at: clock.unix_timestamp,
+            pending_reflections: Vec::new(),
+            rent_funded: false,
+            holder_index,
+            cycle_snapshots: Vec::new(),
+        }
+    }
+
+    /// Rebuilds a legacy account at the current layout as holder `holder_index`.
+    pub fn from_legacy(legacy: LegacyUserPreferences, holder_index: u64) -> Self {
+        Self {
+            owner: legacy.owner,
+            preferred_pool_id: legacy.preferred_pool_id,
+            custom_memo: legacy.custom_memo,
+            is_banned: legacy.is_banned,
+            created_at: legacy.created_at,
+            ..Self::new(legacy.owner, holder_index)
+        }
+    }
+
+    /// Accounts are only grown to the current layout by creation or migrate_user_preferences;
+    /// a shorter one still holds the legacy layout and may carry stale bytes past it.
+    pub fn is_current_layout(info: &AccountInfo) -> bool {
+        info.data_len() >= 8 + Self::INIT_SPACE
+    }
+
+    /// Adds `amount` owed by `distribution`; false when a new entry would not fit.
+    pub fn accrue_pending(&mut self, distribution: Pubkey, amount: u64) -> Result<bool> {
+        if let Some(entry) = self.pending_reflections.iter_mut().find(|entry| entry.distribution == distribution) {
+            entry.amount = entry
+                .amount
+                .checked_add(amount)
+                .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?;
+            return Ok(true);
+        }
+        if self.pending_reflections.len() >= crate::constants::MAX_PENDING_REFLECTIONS {
+            return Ok(false);
+        }
+        self.pending_reflections.push(PendingReflection { distribution, amount });
+        Ok(true)
+    }
+
+    /// Records `balance` for `distribution`'s cycle `cycle_id`, replacing its previous
+    /// snapshot; false when a new entry would not fit.
+    pub fn record_snapshot(&mut self, distribution: Pubkey, cycle_id: u64, balance: u64) -> bool {
+        let snapshot = CycleSnapshot { distribution, cycle_id, balance };
+        if let Some(entry) = self.cycle_snapshots.iter_mut().find(|entry| entry.distribution == distribution) {
+            *entry = snapshot;
+            return true;
+        }
+        if self.cycle_snapshots.len() >= crate::constants::MAX_CYCLE_SNAPSHOTS {
+            return false;
+        }
+        self.cycle_snapshots.push(snapshot);
+        true
+    }
+
+    /// Balance recorded by `distribution` in cycle `cycle_id`, if its sweep recorded one.
+    pub fn snapshot_balance(&self, distribution: Pubkey, cycle_id: u64) -> Option<u64> {
+        self.cycle_snapshots
+            .iter()
+            .find(|entry| entry.distribution == distribution && entry.cycle_id == cycle_id)
+            .map(|entry| entry.balance)
+    }
+
+    /// Removes `distribution`'s snapshot for cycle `cycle_id`; false when there was none.
+    pub fn take_snapshot(&mut self, distribution: Pubkey, cycle_id: u64) -> bool {
+        let before = self.cycle_snapshots.len();
+        self.cycle_snapshots
+            .retain(|entry| entry.distribution != distribution || entry.cycle_id != cycle_id);
+        self.cycle_snapshots.len() < before
+    }
+
+    /// Removes and returns the amount owed by `distribution`.
+    pub fn take_pending(&mut self, distribution: Pubkey) -> u64 {
+        match self.pending_reflections.iter().position(|entry| entry.distribution == distribution) {
+            Some(index) => self.pending_reflections.remove(index).amount,
+            None => 0,
         }
     }
 }
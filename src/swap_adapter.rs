@@ -0,0 +1,181 @@
+// Generic swap adapter interface used by `reflect` to deliver a holder's preferred token.
+//
+// An adapter is any program that owns `TokenPool.pool_address` and exposes a `swap`
+// instruction (Anchor discriminator of "global:swap") taking
+// `(amount_in: u64, minimum_amount_out: u64)` with the account list:
+//
+//   0. pool                  (writable)  - TokenPool.pool_address
+//   1. pool_input_vault      (writable)  - adapter reserve receiving the base asset
+//   2. pool_output_vault     (writable)  - adapter reserve paying the pool token
+//   3. source                (writable)  - program-owned base asset account
+//   4. destination           (writable)  - program-owned intermediate pool token account
+//   5. authority             (signer)    - distribution_config PDA
+//   6. input_token_program
+//   7. output_token_program              - TokenPool.token_program
+//   8. input_mint                        - configured base mint
+//   9. output_mint                       - TokenPool.token_mint
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{self, Mint, TransferChecked};
+use crate::state::TokenPool;
+use crate::token_utils::load_token_account;
+
+// Accounts supplied per swap route in `reflect` remaining accounts:
+// [pool, pool_input_vault, pool_output_vault, adapter_program, output_token_program, output_mint, intermediate]
+pub const SWAP_ROUTE_ACCOUNTS: usize = 7;
+
+// sha256("global:swap")[..8]
+pub const SWAP_IX_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+// Fixed-point scale for SwapRoute.min_out_rate (pool token out per base token in)
+pub const SWAP_RATE_PRECISION: u64 = 1_000_000_000;
+
+pub struct SwapRouteAccounts<'info> {
+    pub pool: AccountInfo<'info>,
+    pub pool_input_vault: AccountInfo<'info>,
+    pub pool_output_vault: AccountInfo<'info>,
+    pub adapter_program: AccountInfo<'info>,
+    pub output_token_program: AccountInfo<'info>,
+    pub output_mint: AccountInfo<'info>,
+    pub intermediate: AccountInfo<'info>, // Pool token account owned by the distribution_config PDA
+}
+
+impl<'info> SwapRouteAccounts<'info> {
+    /// Reads one route group and checks it against the registered pool.
+    pub fn load(accounts: &[AccountInfo<'info>], token_pool: &TokenPool, authority: &Pubkey) -> Result<Self> {
+        require!(
+            accounts.len() == SWAP_ROUTE_ACCOUNTS,
+            crate::errors::SolFlexError::InvalidRemainingAccounts
+        );
+        let route = Self {
+            pool: accounts[0].clone(),
+            pool_input_vault: accounts[1].clone(),
+            pool_output_vault: accounts[2].clone(),
+            adapter_program: accounts[3].clone(),
+            output_token_program: accounts[4].clone(),
+            output_mint: accounts[5].clone(),
+            intermediate: accounts[6].clone(),
+        };
+
+        require!(
+            route.pool.key() == token_pool.pool_address,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+        require!(
+            route.adapter_program.executable && route.adapter_program.key() == *route.pool.owner,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+        require!(
+            route.output_token_program.key() == token_pool.token_program,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+        require!(
+            route.output_mint.key() == token_pool.token_mint,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+
+        require!(
+            *route.intermediate.owner == token_pool.token_program,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+        let intermediate = load_token_account(&route.intermediate)?;
+        require!(
+            intermediate.mint == token_pool.token_mint && intermediate.owner == *authority,
+            crate::errors::SolFlexError::InvalidSwapRoute
+        );
+        Ok(route)
+    }
+
+    /// Minimum pool tokens expected for `amount_in` at `min_out_rate`.
+    pub fn minimum_amount_out(amount_in: u64, min_out_rate: u64) -> Result<u64> {
+        let minimum = (amount_in as u128)
+            .checked_mul(min_out_rate as u128)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow)?
+            / SWAP_RATE_PRECISION as u128;
+        u64::try_from(minimum).map_err(|_| crate::errors::SolFlexError::ArithmeticOverflow.into())
+    }
+
+    /// Swaps `amount_in` from `source` into the intermediate account.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        &self,
+        source: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        input_token_program: &AccountInfo<'info>,
+        input_mint: &AccountInfo<'info>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = Vec::with_capacity(24);
+        data.extend_from_slice(&SWAP_IX_DISCRIMINATOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.adapter_program.key(),
+            accounts: vec![
+                AccountMeta::new(self.pool.key(), false),
+                AccountMeta::new(self.pool_input_vault.key(), false),
+                AccountMeta::new(self.pool_output_vault.key(), false),
+                AccountMeta::new(source.key(), false),
+                AccountMeta::new(self.intermediate.key(), false),
+                AccountMeta::new_readonly(authority.key(), true),
+                AccountMeta::new_readonly(input_token_program.key(), false),
+                AccountMeta::new_readonly(self.output_token_program.key(), false),
+                AccountMeta::new_readonly(input_mint.key(), false),
+                AccountMeta::new_readonly(self.output_mint.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.pool.clone(),
+                self.pool_input_vault.clone(),
+                self.pool_output_vault.clone(),
+                source.clone(),
+                self.intermediate.clone(),
+                authority.clone(),
+                input_token_program.clone(),
+                self.output_token_program.clone(),
+                input_mint.clone(),
+                self.output_mint.clone(),
+                self.adapter_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    /// Pays `amount` of the pool token from the intermediate account to `destination`.
+    pub fn transfer_out(
+        &self,
+        destination: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let decimals = {
+            let data = self.output_mint.try_borrow_data()?;
+            Mint::try_deserialize(&mut &data[..])?.decimals
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.output_token_program.clone(),
+                TransferChecked {
+                    from: self.intermediate.clone(),
+                    mint: self.output_mint.clone(),
+                    to: destination.clone(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            decimals,
+        )
+    }
+}
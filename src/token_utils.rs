@@ -0,0 +1,67 @@
+// Helpers shared by instructions that handle both SPL Token and Token-2022 accounts.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as Token2022Account, Mint as Token2022Mint};
+use anchor_spl::token_interface::TokenAccount;
+
+pub fn load_token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    let data = info.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..])
+}
+
+pub fn token_account_amount(info: &AccountInfo) -> Result<u64> {
+    Ok(load_token_account(info)?.amount)
+}
+
+/// Fee withheld by the transfer-fee extension when sending `amount` of `mint`;
+/// zero for SPL Token mints and Token-2022 mints without the extension.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(crate::errors::SolFlexError::ArithmeticOverflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Amount that arrives at the destination after the transfer fee is withheld.
+pub fn net_transfer_amount(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    Ok(amount.saturating_sub(transfer_fee(mint, amount)?))
+}
+
+/// Data length the associated token program allocates for a new `mint` account:
+/// the base layout for SPL Token, plus the mint's required extensions and
+/// ImmutableOwner for Token-2022.
+pub fn associated_token_account_len(mint: &AccountInfo) -> Result<usize> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(ExtensionType::try_calculate_account_len::<Token2022Account>(&[])?);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    let mut extensions = ExtensionType::get_required_init_account_extensions(&mint_state.get_extension_types()?);
+    if !extensions.contains(&ExtensionType::ImmutableOwner) {
+        extensions.push(ExtensionType::ImmutableOwner);
+    }
+    Ok(ExtensionType::try_calculate_account_len::<Token2022Account>(&extensions)?)
+}
+
+/// Withdraw-withheld authority of a Token-2022 mint with the transfer-fee extension.
+pub fn withdraw_withheld_authority(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    require!(
+        *mint.owner == anchor_spl::token_2022::ID,
+        crate::errors::SolFlexError::InvalidConfig
+    );
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    let fee_config = mint_state
+        .get_extension::<TransferFeeConfig>()
+        .map_err(|_| crate::errors::SolFlexError::InvalidConfig)?;
+    Ok(fee_config.withdraw_withheld_authority.into())
+}
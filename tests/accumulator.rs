@@ -0,0 +1,127 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::BanUserParams;
+use sol_flex::state::{Config, DistributionConfig, DistributionMode, HolderRewards, UserPreferences};
+
+fn accumulator() -> DistributionConfig {
+    let mut distribution = DistributionConfig::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        255,
+    );
+    distribution.distribution_mode = DistributionMode::Accumulator;
+    distribution
+}
+
+#[test]
+fn accrued_rewards_leave_reflection_pool_and_block_mode_switches() {
+    common::setup();
+    let mut distribution = accumulator();
+    let mut holder = HolderRewards::new(Pubkey::new_unique(), Pubkey::new_unique(), 255);
+
+    // Nothing staked yet: the reward waits in unallocated_rewards, still owed.
+    distribution.credit_reflection(1_000).unwrap();
+    assert_eq!(distribution.reflection_pool, 0);
+    assert_eq!(distribution.accrued_liability, 1_000);
+    assert_eq!(distribution.unallocated_rewards, 1_000);
+    assert!(distribution.distribute_reflection(1).is_err());
+
+    holder.staked = 500;
+    distribution.checkpoint_shares(&mut holder, 500).unwrap();
+    distribution.credit_reflection(500).unwrap();
+    assert_eq!(distribution.unallocated_rewards, 0);
+    assert_eq!(distribution.accrued_liability, 1_500);
+    assert_eq!(
+        distribution.set_distribution_mode(DistributionMode::Proportional).unwrap_err(),
+        SolFlexError::SharesOutstanding.into()
+    );
+
+    // Unstaking settles the holder; the settled reward is still owed until paid.
+    distribution.checkpoint_shares(&mut holder, 0).unwrap();
+    assert_eq!(distribution.total_shares, 0);
+    assert_eq!(holder.pending_rewards, 1_500);
+    assert_eq!(
+        distribution.set_distribution_mode(DistributionMode::Proportional).unwrap_err(),
+        SolFlexError::SharesOutstanding.into()
+    );
+
+    distribution.pay_accrued(holder.pending_rewards).unwrap();
+    assert_eq!(distribution.accrued_liability, 0);
+    distribution.set_distribution_mode(DistributionMode::Proportional).unwrap();
+    assert_eq!(distribution.distribution_mode, DistributionMode::Proportional);
+    assert_eq!(distribution.reflection_pool, 0);
+}
+
+#[test]
+fn leaving_accumulator_mode_returns_unallocated_rewards() {
+    common::setup();
+    let mut distribution = accumulator();
+
+    distribution.credit_reflection(700).unwrap();
+    distribution.set_distribution_mode(DistributionMode::Equal).unwrap();
+    assert_eq!(distribution.reflection_pool, 700);
+    assert_eq!(distribution.accrued_liability, 0);
+    assert_eq!(distribution.unallocated_rewards, 0);
+    assert_eq!(distribution.tracked_total().unwrap(), 700);
+}
+
+#[test]
+fn banning_a_staker_settles_and_zeroes_its_shares() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let user = Pubkey::new_unique();
+    ledger.add_wallet(user, 1_000_000_000);
+
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    ledger.add_state(config, sol_flex::ID, &Config::new(Pubkey::new_unique(), config_bump), 8 + Config::INIT_SPACE);
+    let (preferences, _) = Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, user.as_ref()], &sol_flex::ID);
+    ledger.add_state(preferences, sol_flex::ID, &UserPreferences::new(user, 0), 8 + UserPreferences::INIT_SPACE);
+
+    let distribution = Pubkey::new_unique();
+    let mut distribution_state = accumulator();
+    let mut holder_state = HolderRewards::new(distribution, user, 255);
+    holder_state.staked = 400;
+    distribution_state.checkpoint_shares(&mut holder_state, 400).unwrap();
+    distribution_state.credit_reflection(800).unwrap();
+    ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+    let holder = Pubkey::new_unique();
+    ledger.add_state(holder, sol_flex::ID, &holder_state, 8 + HolderRewards::INIT_SPACE);
+
+    let ban = |ban_status: bool| Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::BanUser {
+            config,
+            user_preferences: preferences,
+            distribution_config: Some(distribution),
+            holder_rewards: Some(holder),
+            authority: user,
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::BanUser { params: BanUserParams { user_to_ban: user, ban_status } }.data(),
+    };
+    ledger.process(&ban(true)).unwrap();
+
+    // The stake stops earning at once; what it earned before the ban is kept.
+    let banned: HolderRewards = ledger.state(&holder);
+    assert_eq!(banned.shares, 0);
+    assert_eq!(banned.staked, 400);
+    assert_eq!(banned.pending_rewards, 800);
+    assert_eq!(ledger.state::<DistributionConfig>(&distribution).total_shares, 0);
+
+    // Another user's checkpoint is refused.
+    let mut other = HolderRewards::new(distribution, Pubkey::new_unique(), 255);
+    other.staked = 1;
+    ledger.add_state(holder, sol_flex::ID, &other, 8 + HolderRewards::INIT_SPACE);
+    assert_eq!(ledger.process(&ban(true)).unwrap_err(), error(SolFlexError::InvalidParameters));
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
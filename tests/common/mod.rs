@@ -0,0 +1,61 @@
+// Native harness shared by the integration tests: stubs the sysvar syscalls the
+// program reads (Clock, Rent) and routes CPIs through `runtime`, so state and handlers
+// can run outside a validator.
+
+pub mod runtime;
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Once;
+
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, SUCCESS};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::rent::Rent;
+
+pub const START_TIMESTAMP: i64 = 1_700_000_000;
+
+static UNIX_TIMESTAMP: AtomicI64 = AtomicI64::new(START_TIMESTAMP);
+static SETUP: Once = Once::new();
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        runtime::invoke(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { unix_timestamp: now(), ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+}
+
+pub fn setup() {
+    SETUP.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+}
+
+pub fn now() -> i64 {
+    UNIX_TIMESTAMP.load(Ordering::SeqCst)
+}
+
+#[allow(dead_code)]
+pub fn set_now(unix_timestamp: i64) {
+    UNIX_TIMESTAMP.store(unix_timestamp, Ordering::SeqCst);
+}
@@ -0,0 +1,367 @@
+// In-memory accounts and a minimal instruction processor: runs sol-flex, the mock AMM,
+// spl-token and Token-2022 natively, with cross-program invocations dispatched by `invoke`.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::bpf_loader;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut};
+
+const PROGRAMS: [Pubkey; 2] = [sol_flex::ID, mock_amm::ID];
+
+#[derive(Clone)]
+pub struct TestAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+#[derive(Default)]
+pub struct Ledger {
+    accounts: HashMap<Pubkey, TestAccount>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        let mut ledger = Self::default();
+        for program in [sol_flex::ID, mock_amm::ID, spl_token::ID, spl_token_2022::ID, system_program::ID] {
+            ledger.set(program, TestAccount { lamports: 1, data: Vec::new(), owner: bpf_loader::ID, executable: true });
+        }
+        ledger
+    }
+
+    pub fn set(&mut self, key: Pubkey, account: TestAccount) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn add_wallet(&mut self, key: Pubkey, lamports: u64) {
+        self.set(key, TestAccount { lamports, data: Vec::new(), owner: system_program::ID, executable: false });
+    }
+
+    /// Stores `state` with its discriminator, padded to `space` bytes.
+    pub fn add_state<T: AccountSerialize>(&mut self, key: Pubkey, owner: Pubkey, state: &T, space: usize) {
+        let mut data = Vec::with_capacity(space);
+        state.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= space, "account space too small");
+        data.resize(space, 0);
+        self.set(key, TestAccount { lamports: 1_000_000_000, data, owner, executable: false });
+    }
+
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        T::try_deserialize(&mut &self.accounts[key].data[..]).unwrap()
+    }
+
+    pub fn add_mint(&mut self, key: Pubkey, decimals: u8, supply: u64) {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        self.add_packed(key, mint);
+    }
+
+    pub fn add_token_account(&mut self, key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) {
+        let account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        self.add_packed(key, account);
+    }
+
+    pub fn freeze_token_account(&mut self, key: Pubkey) {
+        let mut account = spl_token::state::Account::unpack(&self.accounts[&key].data).unwrap();
+        account.state = spl_token::state::AccountState::Frozen;
+        self.add_packed(key, account);
+    }
+
+    /// A Token-2022 mint whose transfer-fee extension withholds `fee_bps`, up to `maximum_fee`.
+    pub fn add_fee_mint(
+        &mut self,
+        key: Pubkey,
+        decimals: u8,
+        supply: u64,
+        fee_bps: u16,
+        maximum_fee: u64,
+        withdraw_withheld_authority: Pubkey,
+    ) {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        let mut data = vec![0; len];
+        let mut mint = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data).unwrap();
+        let fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: fee_bps.into(),
+        };
+        let fee_config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        fee_config.withdraw_withheld_authority = Some(withdraw_withheld_authority).try_into().unwrap();
+        fee_config.older_transfer_fee = fee;
+        fee_config.newer_transfer_fee = fee;
+        mint.base = spl_token_2022::state::Mint {
+            mint_authority: COption::None,
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        self.set(key, TestAccount { lamports: 1_000_000_000, data, owner: spl_token_2022::ID, executable: false });
+    }
+
+    /// A Token-2022 account of a fee mint, with `withheld` already withheld on it.
+    pub fn add_fee_token_account(&mut self, key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64, withheld: u64) {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+            ExtensionType::TransferFeeAmount,
+        ])
+        .unwrap();
+        let mut data = vec![0; len];
+        let mut account =
+            StateWithExtensionsMut::<spl_token_2022::state::Account>::unpack_uninitialized(&mut data).unwrap();
+        account.init_extension::<TransferFeeAmount>(true).unwrap().withheld_amount = withheld.into();
+        account.base = spl_token_2022::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        account.pack_base();
+        account.init_account_type().unwrap();
+        self.set(key, TestAccount { lamports: 1_000_000_000, data, owner: spl_token_2022::ID, executable: false });
+    }
+
+    fn add_packed<T: Pack>(&mut self, key: Pubkey, state: T) {
+        let mut data = vec![0; T::LEN];
+        state.pack_into_slice(&mut data);
+        self.set(key, TestAccount { lamports: 1_000_000_000, data, owner: spl_token::ID, executable: false });
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    // Token-2022 layouts extend the SPL Token ones, so both programs' accounts read the same way.
+    pub fn token_amount(&self, key: &Pubkey) -> u64 {
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&self.accounts[key].data).unwrap().base.amount
+    }
+
+    pub fn mint_supply(&self, key: &Pubkey) -> u64 {
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&self.accounts[key].data).unwrap().base.supply
+    }
+
+    /// Transfer fees withheld on a Token-2022 account of a fee mint.
+    pub fn withheld_amount(&self, key: &Pubkey) -> u64 {
+        let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&self.accounts[key].data).unwrap();
+        account.get_extension::<TransferFeeAmount>().unwrap().withheld_amount.into()
+    }
+
+    /// Runs a top-level instruction signed by the metas marked as signers; every account is
+    /// rolled back if it fails.
+    pub fn process(&mut self, instruction: &Instruction) -> ProgramResult {
+        let mut keys: Vec<Pubkey> = Vec::new();
+        for meta in &instruction.accounts {
+            if !keys.contains(&meta.pubkey) {
+                keys.push(meta.pubkey);
+            }
+        }
+        let snapshot: Vec<(Pubkey, Option<TestAccount>)> =
+            keys.iter().map(|key| (*key, self.accounts.get(key).cloned())).collect();
+        let mut entries: Vec<(Pubkey, TestAccount)> = keys
+            .iter()
+            .map(|key| {
+                let account = self.accounts.remove(key).unwrap_or(TestAccount {
+                    lamports: 0,
+                    data: Vec::new(),
+                    owner: system_program::ID,
+                    executable: false,
+                });
+                (*key, account)
+            })
+            .collect();
+        let mut buffers: Vec<Serialized> = entries.iter().map(|(key, account)| Serialized::new(key, &account.data)).collect();
+
+        let result = {
+            let unique: Vec<AccountInfo> = entries
+                .iter_mut()
+                .zip(buffers.iter_mut())
+                .map(|((key, account), buffer)| {
+                    let metas = instruction.accounts.iter().filter(|meta| meta.pubkey == *key);
+                    let (is_signer, is_writable) =
+                        metas.fold((false, false), |(s, w), meta| (s || meta.is_signer, w || meta.is_writable));
+                    let (key, data) = buffer.parts();
+                    AccountInfo::new(
+                        key,
+                        is_signer,
+                        is_writable,
+                        &mut account.lamports,
+                        data,
+                        &account.owner,
+                        account.executable,
+                        0,
+                    )
+                })
+                .collect();
+            let infos: Vec<AccountInfo> = instruction
+                .accounts
+                .iter()
+                .map(|meta| unique[keys.iter().position(|key| *key == meta.pubkey).unwrap()].clone())
+                .collect();
+            dispatch(&instruction.program_id, &infos, &instruction.data)
+        };
+
+        for ((_, account), buffer) in entries.iter_mut().zip(&buffers) {
+            account.data = buffer.data().to_vec();
+        }
+        self.accounts.extend(entries);
+        if result.is_err() {
+            for (key, account) in snapshot {
+                match account {
+                    Some(account) => self.accounts.insert(key, account),
+                    None => self.accounts.remove(&key),
+                };
+            }
+        }
+        result
+    }
+}
+
+/// An account's key and data laid out as the loader serializes them, so `AccountInfo::realloc`
+/// finds the original length before the key and the current length before the data.
+struct Serialized {
+    key: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl Serialized {
+    fn new(key: &Pubkey, data: &[u8]) -> Self {
+        let mut key_buffer = (data.len() as u32).to_ne_bytes().to_vec();
+        key_buffer.extend_from_slice(key.as_ref());
+        let mut data_buffer = (data.len() as u64).to_ne_bytes().to_vec();
+        data_buffer.extend_from_slice(data);
+        data_buffer.resize(data_buffer.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        Self { key: key_buffer, data: data_buffer }
+    }
+
+    fn len(&self) -> usize {
+        u64::from_ne_bytes(self.data[..8].try_into().unwrap()) as usize
+    }
+
+    fn parts(&mut self) -> (&Pubkey, &mut [u8]) {
+        let len = self.len();
+        // Both views come from the whole buffers, so realloc may grow the data into the padding.
+        unsafe {
+            let key = &*(self.key.as_ptr().add(4) as *const Pubkey);
+            let data = std::slice::from_raw_parts_mut(self.data.as_mut_ptr().add(8), len);
+            (key, data)
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data[8..8 + self.len()]
+    }
+}
+
+fn dispatch<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>], data: &[u8]) -> ProgramResult {
+    if *program_id == sol_flex::ID {
+        sol_flex::entry(program_id, accounts, data)
+    } else if *program_id == mock_amm::ID {
+        mock_amm::entry(program_id, accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == system_program::ID {
+        system(accounts, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+/// The system instructions programs invoke: create_account (0), assign (1), transfer (2)
+/// and allocate (8).
+fn system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let tag = u32::from_le_bytes(data[..4].try_into().unwrap());
+    let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    let pubkey_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+    let debit = |from: &AccountInfo, to: &AccountInfo, lamports: u64| -> ProgramResult {
+        if !from.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let remaining = from.lamports().checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+        **from.try_borrow_mut_lamports()? = remaining;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    };
+    match tag {
+        0 => {
+            let (from, to) = (&accounts[0], &accounts[1]);
+            if !to.is_signer || !to.data_is_empty() || to.lamports() > 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            debit(from, to, u64_at(4))?;
+            to.realloc(u64_at(12) as usize, true)?;
+            to.assign(&pubkey_at(20));
+            Ok(())
+        }
+        1 => {
+            accounts[0].assign(&pubkey_at(4));
+            Ok(())
+        }
+        2 => debit(&accounts[0], &accounts[1], u64_at(4)),
+        8 => accounts[0].realloc(u64_at(4) as usize, true),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Cross-program invocation: hands the callee the caller's accounts, with PDA signers
+/// checked against the seeds of the known programs.
+pub fn invoke<'a>(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo<'a>],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let pda_signers: Vec<Pubkey> = signers_seeds
+        .iter()
+        .flat_map(|seeds| PROGRAMS.iter().filter_map(move |program| Pubkey::create_program_address(seeds, program).ok()))
+        .collect();
+    let mut infos = Vec::with_capacity(instruction.accounts.len());
+    for meta in &instruction.accounts {
+        let mut info = account_infos
+            .iter()
+            .find(|info| *info.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .clone();
+        if meta.is_signer && !info.is_signer && !pda_signers.contains(&meta.pubkey) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if meta.is_writable && !info.is_writable {
+            return Err(ProgramError::InvalidArgument);
+        }
+        info.is_signer = meta.is_signer;
+        info.is_writable = meta.is_writable;
+        infos.push(info);
+    }
+    // The callee needs `&'a [AccountInfo<'a>]`; the handful of leaked infos per CPI is fine in tests.
+    let infos: &'a [AccountInfo<'a>] = Box::leak(infos.into_boxed_slice());
+    dispatch(&instruction.program_id, infos, &instruction.data)
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{AddDepositorParams, DepositFeesParams, RecordFeesParams, RemoveDepositorParams};
+use sol_flex::state::{Config, DistributionConfig, Role};
+
+const SUPPLY: u64 = 1_000_000;
+
+// One distribution whose fee vault already holds the fees about to be recorded.
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    treasurer: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    token_mint: Pubkey,
+    fee_vault: Pubkey,
+    project_account: Pubkey,
+}
+
+impl Fixture {
+    fn new(vault: u64) -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let treasurer = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_wallet(treasurer, 1_000_000_000);
+        ledger.add_mint(token_mint, 6, SUPPLY);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.grant_role(treasurer, Role::Treasurer).unwrap();
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, distribution_bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        let fee_vault = Pubkey::new_unique();
+        let project_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, project_account, Pubkey::new_unique(), distribution_bump);
+        distribution_state.reflection_rate = 7_000;
+        distribution_state.burn_rate = 2_000;
+        distribution_state.project_rate = 500;
+        distribution_state.dev_fee_rate = 500;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_token_account(fee_vault, token_mint, distribution, vault);
+        ledger.add_token_account(project_account, token_mint, Pubkey::new_unique(), 0);
+
+        Self { ledger, authority, treasurer, config, distribution, token_mint, fee_vault, project_account }
+    }
+
+    fn record_fees(&mut self, authority: Pubkey, amount: u64) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::RecordFees {
+                config: self.config,
+                distribution_config: self.distribution,
+                fee_vault: self.fee_vault,
+                token_mint: self.token_mint,
+                authority,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::RecordFees { params: RecordFeesParams { amount } }.data(),
+        })
+    }
+
+    fn deposit_fees(
+        &mut self,
+        depositor: Pubkey,
+        source: Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::DepositFees {
+                config: self.config,
+                distribution_config: self.distribution,
+                fee_vault: self.fee_vault,
+                token_mint: self.token_mint,
+                depositor_token_account: source,
+                depositor,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::DepositFees { params: DepositFeesParams { amount } }.data(),
+        })
+    }
+
+    fn withdraw_project(&mut self, authority: Pubkey) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::WithdrawProject {
+                config: self.config,
+                distribution_config: self.distribution,
+                fee_vault: self.fee_vault,
+                token_mint: self.token_mint,
+                project_token_account: self.project_account,
+                authority,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::WithdrawProject {}.data(),
+        })
+    }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn recorded_fees_burn_the_burn_share_from_the_vault() {
+    let mut fixture = Fixture::new(10_000);
+
+    let stranger = Pubkey::new_unique();
+    fixture.ledger.add_wallet(stranger, 1_000_000_000);
+    assert_eq!(fixture.record_fees(stranger, 10_000).unwrap_err(), error(SolFlexError::Unauthorized));
+
+    fixture.record_fees(fixture.authority, 10_000).unwrap();
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 8_000);
+    assert_eq!(fixture.ledger.mint_supply(&fixture.token_mint), SUPPLY - 2_000);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.burn_pool, 0);
+    assert_eq!(distribution.total_burned, 2_000);
+    assert_eq!(distribution.reflection_pool, 7_000);
+    assert_eq!(distribution.tracked_total().unwrap(), 8_000);
+
+    // The next batch burns only its own share.
+    fixture.ledger.add_token_account(fixture.fee_vault, fixture.token_mint, fixture.distribution, 8_000 + 500);
+    fixture.record_fees(fixture.authority, 500).unwrap();
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 8_400);
+    assert_eq!(fixture.ledger.mint_supply(&fixture.token_mint), SUPPLY - 2_100);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.total_burned, 2_100);
+}
+
+#[test]
+fn treasurers_withdraw_the_project_share_to_the_project_account() {
+    let mut fixture = Fixture::new(10_000);
+    fixture.record_fees(fixture.authority, 10_000).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    fixture.ledger.add_wallet(stranger, 1_000_000_000);
+    assert_eq!(fixture.withdraw_project(stranger).unwrap_err(), error(SolFlexError::Unauthorized));
+
+    fixture.withdraw_project(fixture.treasurer).unwrap();
+    assert_eq!(fixture.ledger.token_amount(&fixture.project_account), 500);
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 7_500);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.project_pool, 0);
+    assert_eq!(distribution.reflection_pool, 7_000);
+    assert_eq!(distribution.tracked_total().unwrap(), 7_500);
+
+    // Nothing is left to withdraw until more fees are recorded.
+    assert_eq!(fixture.withdraw_project(fixture.treasurer).unwrap_err(), error(SolFlexError::InsufficientFunds));
+}
+
+#[test]
+fn only_allowlisted_depositors_deposit_and_their_fees_are_split() {
+    let mut fixture = Fixture::new(0);
+    let depositor = Pubkey::new_unique();
+    let source = Pubkey::new_unique();
+    fixture.ledger.add_wallet(depositor, 1_000_000_000);
+    fixture.ledger.add_token_account(source, fixture.token_mint, depositor, 5_000);
+    assert_eq!(
+        fixture.deposit_fees(depositor, source, 1_000).unwrap_err(),
+        error(SolFlexError::DepositorNotAllowed)
+    );
+
+    fixture
+        .ledger
+        .process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::AddDepositor { config: fixture.config, authority: fixture.authority }
+                .to_account_metas(None),
+            data: sol_flex::instruction::AddDepositor { params: AddDepositorParams { depositor } }.data(),
+        })
+        .unwrap();
+    fixture.deposit_fees(depositor, source, 1_000).unwrap();
+    assert_eq!(fixture.ledger.token_amount(&source), 4_000);
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 800);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.total_fees, 1_000);
+    assert_eq!(distribution.reflection_pool, 700);
+    assert_eq!(distribution.total_burned, 200);
+    assert_eq!(distribution.tracked_total().unwrap(), 800);
+
+    // Depositors cannot spend another holder's tokens, and lose access once removed.
+    let other = Pubkey::new_unique();
+    fixture.ledger.add_token_account(other, fixture.token_mint, Pubkey::new_unique(), 5_000);
+    assert_eq!(
+        fixture.deposit_fees(depositor, other, 1_000).unwrap_err(),
+        error(SolFlexError::InvalidTokenAccount)
+    );
+    fixture
+        .ledger
+        .process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::RemoveDepositor { config: fixture.config, authority: fixture.authority }
+                .to_account_metas(None),
+            data: sol_flex::instruction::RemoveDepositor { params: RemoveDepositorParams { depositor } }.data(),
+        })
+        .unwrap();
+    assert_eq!(
+        fixture.deposit_fees(depositor, source, 1_000).unwrap_err(),
+        error(SolFlexError::DepositorNotAllowed)
+    );
+}
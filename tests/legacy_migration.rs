@@ -0,0 +1,313 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+use common::runtime::{Ledger, TestAccount};
+use sol_flex::constants::CONFIG_VERSION;
+use sol_flex::errors::SolFlexError;
+use sol_flex::state::{
+    Config, DistributionConfig, LegacyConfig, LegacyDistributionConfig, LegacyUserPreferences, UserPreferences,
+};
+
+fn legacy(token_mint: Pubkey) -> LegacyDistributionConfig {
+    LegacyDistributionConfig {
+        token_mint,
+        fee_vault: Pubkey::new_unique(),
+        reflection_pool: 900,
+        dev_pool: 20,
+        total_fees: 10_000,
+        last_paid: Pubkey::default(),
+        limit: 100,
+        reflection_rate: 1000,
+        burn_rate: 200,
+        project_rate: 200,
+        project_account: Pubkey::new_unique(),
+        dev_fee_rate: 20,
+        dev_account: Pubkey::new_unique(),
+        created_at: 1,
+        updated_at: 2,
+        bump: 254,
+    }
+}
+
+fn parse(data: &mut [u8]) -> Result<LegacyDistributionConfig> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, data, &sol_flex::ID, false, 0);
+    LegacyDistributionConfig::try_from_account(&info)
+}
+
+#[test]
+fn legacy_singleton_layout_is_parsed() {
+    let token_mint = Pubkey::new_unique();
+    let mut data = DistributionConfig::DISCRIMINATOR.to_vec();
+    legacy(token_mint).serialize(&mut data).unwrap();
+
+    let parsed = parse(&mut data).unwrap();
+    assert_eq!(parsed.token_mint, token_mint);
+    assert_eq!(parsed.reflection_pool, 900);
+    assert_eq!(parsed.dev_pool, 20);
+    assert_eq!(parsed.bump, 254);
+}
+
+#[test]
+fn legacy_parse_rejects_other_accounts() {
+    let mut data = [0u8; 8].to_vec();
+    legacy(Pubkey::new_unique()).serialize(&mut data).unwrap();
+    assert_eq!(parse(&mut data).err().unwrap(), SolFlexError::InvalidConfig.into());
+
+    let mut truncated = DistributionConfig::DISCRIMINATOR.to_vec();
+    truncated.extend_from_slice(&[0u8; 16]);
+    assert_eq!(parse(&mut truncated).err().unwrap(), SolFlexError::InvalidConfig.into());
+}
+
+// Account sizes written by the first deployment: 8 + INIT_SPACE of the legacy layouts.
+const LEGACY_CONFIG_SPACE: usize = 8 + 32 + 1 + (4 + 100 * 32) + 1 + 8 + 8 + 8 + 8 + 1;
+const LEGACY_USER_PREFERENCES_SPACE: usize = 8 + 32 + 8 + (4 + 200) + 1 + 8 + 8;
+
+// Stores `state` after `discriminator`, padded with `tail` (non-zero for bytes a shrunken Vec
+// or String left behind).
+fn add_legacy<T: AnchorSerialize>(
+    ledger: &mut Ledger,
+    key: Pubkey,
+    discriminator: [u8; 8],
+    state: &T,
+    space: usize,
+    tail: u8,
+) {
+    let mut data = discriminator.to_vec();
+    state.serialize(&mut data).unwrap();
+    data.resize(space, tail);
+    let lamports = Rent::default().minimum_balance(space);
+    ledger.set(key, TestAccount { lamports, data, owner: sol_flex::ID, executable: false });
+}
+
+fn legacy_config(authority: Pubkey, bump: u8) -> LegacyConfig {
+    LegacyConfig {
+        authority,
+        version: 1,
+        blocklist: vec![Pubkey::new_unique()],
+        is_initialized: true,
+        created_at: 1,
+        updated_at: 2,
+        min_reflection_pool: 500,
+        min_reflection_per_account: 5,
+        bump,
+    }
+}
+
+fn migrate_config(config: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::MigrateConfig {
+            config,
+            authority,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::MigrateConfig {}.data(),
+    }
+}
+
+fn migrate_user_preferences(config: Pubkey, user_preferences: Pubkey, user: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::MigrateUserPreferences {
+            config,
+            user_preferences,
+            user,
+            payer,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::MigrateUserPreferences {}.data(),
+    }
+}
+
+#[test]
+fn legacy_config_is_migrated_once_by_its_authority() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let authority = Pubkey::new_unique();
+    let stranger = Pubkey::new_unique();
+    ledger.add_wallet(authority, 1_000_000_000);
+    ledger.add_wallet(stranger, 1_000_000_000);
+    let (config, bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    let legacy = legacy_config(authority, bump);
+    add_legacy(&mut ledger, config, Config::DISCRIMINATOR, &legacy, LEGACY_CONFIG_SPACE, 0xab);
+
+    assert_eq!(
+        ledger.process(&migrate_config(config, stranger)).unwrap_err(),
+        error(SolFlexError::Unauthorized)
+    );
+    ledger.process(&migrate_config(config, authority)).unwrap();
+
+    let migrated: Config = ledger.state(&config);
+    assert_eq!(migrated.version, CONFIG_VERSION);
+    assert_eq!(migrated.authority, authority);
+    assert_eq!(migrated.blocklist, legacy.blocklist);
+    assert_eq!(migrated.min_reflection_pool, 500);
+    assert_eq!(migrated.min_reflection_per_account, 5);
+    assert_eq!(migrated.created_at, 1);
+    assert_eq!(migrated.bump, bump);
+    // The stale tail is not read back as the appended fields.
+    assert!(migrated.roles.is_empty() && migrated.signers.is_empty() && migrated.depositors.is_empty());
+    assert_eq!((migrated.threshold, migrated.holder_count, migrated.guardian), (0, 0, None));
+    assert_eq!(ledger.lamports(&config), Rent::default().minimum_balance(8 + Config::INIT_SPACE));
+
+    assert_eq!(
+        ledger.process(&migrate_config(config, authority)).unwrap_err(),
+        error(SolFlexError::AlreadyMigrated)
+    );
+}
+
+#[test]
+fn legacy_user_preferences_join_the_holder_registry() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let authority = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    ledger.add_wallet(authority, 1_000_000_000);
+    ledger.add_wallet(payer, 1_000_000_000);
+    let (config, bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    add_legacy(
+        &mut ledger,
+        config,
+        Config::DISCRIMINATOR,
+        &legacy_config(authority, bump),
+        LEGACY_CONFIG_SPACE,
+        0,
+    );
+
+    let users = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let preferences: Vec<Pubkey> = users
+        .iter()
+        .map(|user| {
+            let (key, _) = Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, user.as_ref()], &sol_flex::ID);
+            let legacy = LegacyUserPreferences {
+                owner: *user,
+                preferred_pool_id: 7,
+                custom_memo: "memo".to_string(),
+                is_banned: true,
+                created_at: 3,
+                updated_at: 4,
+            };
+            add_legacy(
+                &mut ledger,
+                key,
+                UserPreferences::DISCRIMINATOR,
+                &legacy,
+                LEGACY_USER_PREFERENCES_SPACE,
+                0xab,
+            );
+            key
+        })
+        .collect();
+
+    // Config goes first: nothing runs against the legacy layout.
+    assert_eq!(
+        ledger
+            .process(&migrate_user_preferences(config, preferences[0], users[0], payer))
+            .unwrap_err(),
+        error(SolFlexError::AccountNotMigrated)
+    );
+    ledger.process(&migrate_config(config, authority)).unwrap();
+
+    for (index, (user, key)) in users.iter().zip(&preferences).enumerate() {
+        ledger.process(&migrate_user_preferences(config, *key, *user, payer)).unwrap();
+        let migrated: UserPreferences = ledger.state(key);
+        assert_eq!(migrated.owner, *user);
+        assert_eq!(migrated.preferred_pool_id, 7);
+        assert_eq!(migrated.custom_memo, "memo");
+        assert!(migrated.is_banned);
+        assert_eq!(migrated.created_at, 3);
+        assert!(migrated.pending_reflections.is_empty() && !migrated.rent_funded);
+        assert_eq!(migrated.holder_index, index as u64);
+    }
+    assert_eq!(ledger.state::<Config>(&config).holder_count, 2);
+
+    assert_eq!(
+        ledger
+            .process(&migrate_user_preferences(config, preferences[0], users[0], payer))
+            .unwrap_err(),
+        error(SolFlexError::AlreadyMigrated)
+    );
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn legacy_vault_moves_into_its_per_mint_distribution_after_config_migration() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let authority = Pubkey::new_unique();
+    ledger.add_wallet(authority, 1_000_000_000);
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    add_legacy(
+        &mut ledger,
+        config,
+        Config::DISCRIMINATOR,
+        &legacy_config(authority, config_bump),
+        LEGACY_CONFIG_SPACE,
+        0,
+    );
+
+    let mint = Pubkey::new_unique();
+    ledger.add_mint(mint, 6, 1_000_000);
+    let (legacy_distribution, legacy_bump) =
+        Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX], &sol_flex::ID);
+    let legacy_fee_vault = Pubkey::new_unique();
+    ledger.add_token_account(legacy_fee_vault, mint, legacy_distribution, 1_000);
+    let legacy_state = LegacyDistributionConfig { fee_vault: legacy_fee_vault, bump: legacy_bump, ..legacy(mint) };
+    add_legacy(
+        &mut ledger,
+        legacy_distribution,
+        DistributionConfig::DISCRIMINATOR,
+        &legacy_state,
+        8 + legacy_state.try_to_vec().unwrap().len(),
+        0,
+    );
+
+    let (distribution, bump) =
+        Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, mint.as_ref()], &sol_flex::ID);
+    let fee_vault = Pubkey::new_unique();
+    ledger.add_token_account(fee_vault, mint, distribution, 0);
+    let distribution_state =
+        DistributionConfig::new(mint, fee_vault, Pubkey::new_unique(), Pubkey::new_unique(), bump);
+    ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+
+    let migrate = Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::MigrateLegacyDistribution {
+            config,
+            legacy_distribution,
+            distribution_config: distribution,
+            legacy_fee_vault,
+            fee_vault,
+            token_mint: mint,
+            authority,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::MigrateLegacyDistribution {}.data(),
+    };
+
+    // The legacy Config is never read with the current layout.
+    assert_eq!(ledger.process(&migrate).unwrap_err(), error(SolFlexError::AccountNotMigrated));
+    ledger.process(&migrate_config(config, authority)).unwrap();
+    ledger.process(&migrate).unwrap();
+
+    assert_eq!(ledger.token_amount(&fee_vault), 1_000);
+    let migrated: DistributionConfig = ledger.state(&distribution);
+    assert_eq!(migrated.dev_pool, 20);
+    assert_eq!(migrated.reflection_pool, 980);
+    assert_eq!(migrated.total_fees, 10_000);
+    assert_eq!(ledger.lamports(&legacy_fee_vault), 0);
+    assert_eq!(ledger.lamports(&legacy_distribution), 0);
+
+    // The legacy PDA is gone, so the migration cannot run twice.
+    assert!(ledger.process(&migrate).is_err());
+}
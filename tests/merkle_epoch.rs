@@ -0,0 +1,183 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::ClaimReflectionParams;
+use sol_flex::state::{Config, DistributionConfig, DistributionEpoch, UserPreferences};
+
+fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[&[1u8], &left, &right]).0
+}
+
+struct Tree {
+    owners: [Pubkey; 3],
+    amounts: [u64; 3],
+    epoch: DistributionEpoch,
+    proofs: [Vec<[u8; 32]>; 3],
+}
+
+// Three leaves: root = parent(parent(l0, l1), l2).
+fn tree() -> Tree {
+    common::setup();
+    let owners = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let amounts = [100, 250, 650];
+    let leaves: Vec<[u8; 32]> = (0..3)
+        .map(|i| DistributionEpoch::leaf(i as u32, &owners[i], amounts[i]))
+        .collect();
+    let left = parent(leaves[0], leaves[1]);
+    let root = parent(left, leaves[2]);
+    Tree {
+        owners,
+        amounts,
+        epoch: DistributionEpoch::new(1, root, 1_000, 3, common::now() + 100, 255),
+        proofs: [vec![leaves[1], leaves[2]], vec![leaves[0], leaves[2]], vec![left]],
+    }
+}
+
+#[test]
+fn verify_proof_accepts_every_published_leaf() {
+    let tree = tree();
+    for i in 0..3 {
+        let leaf = DistributionEpoch::leaf(i as u32, &tree.owners[i], tree.amounts[i]);
+        assert!(tree.epoch.verify_proof(&tree.proofs[i], leaf));
+    }
+}
+
+#[test]
+fn verify_proof_rejects_a_wrong_index() {
+    let tree = tree();
+    let leaf = DistributionEpoch::leaf(1, &tree.owners[0], tree.amounts[0]);
+    assert!(!tree.epoch.verify_proof(&tree.proofs[0], leaf));
+}
+
+#[test]
+fn verify_proof_rejects_a_wrong_amount() {
+    let tree = tree();
+    let leaf = DistributionEpoch::leaf(2, &tree.owners[2], tree.amounts[2] + 1);
+    assert!(!tree.epoch.verify_proof(&tree.proofs[2], leaf));
+}
+
+#[test]
+fn claimed_bitmap_is_bounded_by_the_leaf_count() {
+    common::setup();
+    let mut epoch = DistributionEpoch::new(1, [0; 32], 1_000, 9, common::now() + 100, 255);
+    assert_eq!(epoch.claimed_bitmap.len(), 2);
+
+    epoch.set_claimed(7).unwrap();
+    epoch.set_claimed(8).unwrap();
+    assert!(epoch.is_claimed(7) && epoch.is_claimed(8));
+    assert!(!epoch.is_claimed(0));
+    assert_eq!(epoch.set_claimed(8).unwrap_err(), SolFlexError::AlreadyClaimed.into());
+
+    // Index 9 would still fit the second bitmap byte but is past the last leaf.
+    assert_eq!(epoch.set_claimed(9).unwrap_err(), SolFlexError::InvalidParameters.into());
+    assert_eq!(epoch.set_claimed(u32::MAX).unwrap_err(), SolFlexError::InvalidParameters.into());
+    assert!(!epoch.is_claimed(u32::MAX));
+}
+
+#[test]
+fn unclaimed_amount_is_only_reclaimable_after_the_deadline() {
+    let mut tree = tree();
+    let deadline = tree.epoch.claim_deadline;
+    tree.epoch.claimed_amount = tree.amounts[0];
+
+    assert!(tree.epoch.is_open(deadline));
+    assert_eq!(
+        tree.epoch.unclaimed_amount(deadline).unwrap_err(),
+        SolFlexError::ClaimWindowOpen.into()
+    );
+    assert!(!tree.epoch.is_open(deadline + 1));
+    assert_eq!(tree.epoch.unclaimed_amount(deadline + 1).unwrap(), 900);
+}
+
+// Publishes `tree` as epoch 1 of a distribution funded with its total, with claimant 0 set up.
+fn claim_setup(tree: &Tree, banned: bool, blocklisted: bool) -> (Ledger, Instruction) {
+    let mut ledger = Ledger::new();
+    let claimant = tree.owners[0];
+    let token_mint = Pubkey::new_unique();
+    ledger.add_mint(token_mint, 6, 1_000_000);
+
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    let mut config_state = Config::new(Pubkey::new_unique(), config_bump);
+    if blocklisted {
+        config_state.add_to_blocklist(claimant).unwrap();
+    }
+    ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+    let (distribution, distribution_bump) =
+        Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+    let fee_vault = Pubkey::new_unique();
+    let mut distribution_state =
+        DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), Pubkey::new_unique(), distribution_bump);
+    distribution_state.claims_pool = tree.epoch.total_amount;
+    ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+    ledger.add_token_account(fee_vault, token_mint, distribution, tree.epoch.total_amount);
+
+    let (epoch, epoch_bump) = Pubkey::find_program_address(
+        &[DistributionEpoch::SEED_PREFIX, distribution.as_ref(), 1u64.to_le_bytes().as_ref()],
+        &sol_flex::ID,
+    );
+    let epoch_state = DistributionEpoch::new(
+        1,
+        tree.epoch.merkle_root,
+        tree.epoch.total_amount,
+        tree.epoch.num_leaves,
+        tree.epoch.claim_deadline,
+        epoch_bump,
+    );
+    ledger.add_state(epoch, sol_flex::ID, &epoch_state, 8 + DistributionEpoch::INIT_SPACE);
+
+    let (preferences, _) =
+        Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, claimant.as_ref()], &sol_flex::ID);
+    let mut preferences_state = UserPreferences::new(claimant, 0);
+    preferences_state.is_banned = banned;
+    ledger.add_state(preferences, sol_flex::ID, &preferences_state, 8 + UserPreferences::INIT_SPACE);
+    let claimant_token_account = Pubkey::new_unique();
+    ledger.add_token_account(claimant_token_account, token_mint, claimant, 0);
+
+    let instruction = Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::ClaimReflection {
+            config,
+            distribution_config: distribution,
+            distribution_epoch: epoch,
+            user_preferences: preferences,
+            fee_vault,
+            token_mint,
+            claimant_token_account,
+            claimant,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::ClaimReflection {
+            params: ClaimReflectionParams {
+                epoch: 1,
+                index: 0,
+                amount: tree.amounts[0],
+                proof: tree.proofs[0].clone(),
+            },
+        }
+        .data(),
+    };
+    (ledger, instruction)
+}
+
+#[test]
+fn claim_reflection_refuses_banned_and_blocklisted_owners() {
+    let tree = tree();
+    let unauthorized: ProgramError = anchor_lang::error::Error::from(SolFlexError::Unauthorized).into();
+
+    let (mut ledger, claim) = claim_setup(&tree, true, false);
+    assert_eq!(ledger.process(&claim).unwrap_err(), unauthorized);
+    let (mut ledger, claim) = claim_setup(&tree, false, true);
+    assert_eq!(ledger.process(&claim).unwrap_err(), unauthorized);
+
+    let (mut ledger, claim) = claim_setup(&tree, false, false);
+    ledger.process(&claim).unwrap();
+    assert_eq!(ledger.token_amount(&claim.accounts[6].pubkey), tree.amounts[0]);
+}
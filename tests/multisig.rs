@@ -0,0 +1,164 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{CreateProposalParams, QueueConfigChangeParams};
+use sol_flex::state::{
+    AdminAction, Config, ConfigChange, DistributionConfig, PendingConfigChange, Proposal, ReflectThrottle,
+};
+
+// A 2-of-3 multisig governing two distributions.
+struct Fixture {
+    ledger: Ledger,
+    signers: [Pubkey; 3],
+    config: Pubkey,
+    distributions: [Pubkey; 2],
+}
+
+impl Fixture {
+    fn new() -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        for signer in signers {
+            ledger.add_wallet(signer, 1_000_000_000);
+        }
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(signers[0], config_bump);
+        config_state.set_multisig(signers.to_vec(), 2).unwrap();
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let distributions = [(); 2].map(|_| {
+            let token_mint = Pubkey::new_unique();
+            let (distribution, bump) =
+                Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+            let state = DistributionConfig::new(
+                token_mint,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                bump,
+            );
+            ledger.add_state(distribution, sol_flex::ID, &state, 8 + DistributionConfig::INIT_SPACE);
+            distribution
+        });
+
+        Self { ledger, signers, config, distributions }
+    }
+
+    fn create_proposal(&mut self, params_hash: [u8; 32]) -> Pubkey {
+        let config: Config = self.ledger.state(&self.config);
+        let (proposal, _) = Pubkey::find_program_address(
+            &[Proposal::SEED_PREFIX, config.proposal_count.to_le_bytes().as_ref()],
+            &sol_flex::ID,
+        );
+        let instruction = Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::CreateProposal {
+                config: self.config,
+                proposal,
+                proposer: self.signers[0],
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::CreateProposal {
+                params: CreateProposalParams { action: AdminAction::QueueConfigChange, params_hash },
+            }
+            .data(),
+        };
+        self.ledger.process(&instruction).unwrap();
+        proposal
+    }
+
+    fn approve(&mut self, proposal: Pubkey, signer: Pubkey) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::ApproveProposal { config: self.config, proposal, signer }
+                .to_account_metas(None),
+            data: sol_flex::instruction::ApproveProposal {}.data(),
+        })
+    }
+
+    fn queue(&mut self, distribution: Pubkey, proposal: Pubkey) -> std::result::Result<(), ProgramError> {
+        let config: Config = self.ledger.state(&self.config);
+        let (pending_change, _) = Pubkey::find_program_address(
+            &[PendingConfigChange::SEED_PREFIX, config.change_count.to_le_bytes().as_ref()],
+            &sol_flex::ID,
+        );
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::QueueConfigChange {
+                config: self.config,
+                distribution_config: Some(distribution),
+                pending_change,
+                proposal: Some(proposal),
+                authority: self.signers[2],
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::QueueConfigChange { params: throttle() }.data(),
+        })
+    }
+}
+
+fn throttle() -> QueueConfigChangeParams {
+    QueueConfigChangeParams { change: ConfigChange::Throttle(ReflectThrottle::DrainAll) }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn proposals_execute_once_at_the_threshold_for_their_accounts_only() {
+    let mut fixture = Fixture::new();
+    let [first, second] = fixture.distributions;
+    let params_hash = Proposal::hash_params(&[fixture.config, first], &throttle()).unwrap();
+    let proposal = fixture.create_proposal(params_hash);
+
+    // Only the proposer has approved: 1 of 2.
+    assert_eq!(fixture.queue(first, proposal).unwrap_err(), error(SolFlexError::ThresholdNotMet));
+
+    fixture.approve(proposal, fixture.signers[1]).unwrap();
+    // The same params against the other distribution hash differently.
+    assert_eq!(fixture.queue(second, proposal).unwrap_err(), error(SolFlexError::ProposalMismatch));
+
+    fixture.queue(first, proposal).unwrap();
+    let config: Config = fixture.ledger.state(&fixture.config);
+    assert_eq!(config.change_count, 1);
+    let (pending_change, _) =
+        Pubkey::find_program_address(&[PendingConfigChange::SEED_PREFIX, 0u64.to_le_bytes().as_ref()], &sol_flex::ID);
+    let pending: PendingConfigChange = fixture.ledger.state(&pending_change);
+    assert_eq!(pending.target, first);
+    let proposal_state: Proposal = fixture.ledger.state(&proposal);
+    assert!(proposal_state.executed);
+
+    assert_eq!(fixture.queue(first, proposal).unwrap_err(), error(SolFlexError::ProposalAlreadyExecuted));
+    let config: Config = fixture.ledger.state(&fixture.config);
+    assert_eq!(config.change_count, 1);
+}
+
+#[test]
+fn expired_proposals_cannot_execute() {
+    let mut fixture = Fixture::new();
+    let first = fixture.distributions[0];
+    let params_hash = Proposal::hash_params(&[fixture.config, first], &throttle()).unwrap();
+    let proposal = fixture.create_proposal(params_hash);
+    fixture.approve(proposal, fixture.signers[1]).unwrap();
+
+    // Age the proposal past its lifetime rather than moving the shared clock.
+    let mut proposal_state: Proposal = fixture.ledger.state(&proposal);
+    proposal_state.expires_at = common::now();
+    fixture
+        .ledger
+        .add_state(proposal, sol_flex::ID, &proposal_state, 8 + Proposal::INIT_SPACE);
+
+    assert_eq!(fixture.queue(first, proposal).unwrap_err(), error(SolFlexError::ProposalExpired));
+    assert_eq!(fixture.approve(proposal, fixture.signers[2]).unwrap_err(), error(SolFlexError::ProposalExpired));
+    let config: Config = fixture.ledger.state(&fixture.config);
+    assert_eq!(config.change_count, 0);
+}
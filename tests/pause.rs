@@ -0,0 +1,308 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{
+    AddPoolParams, RecordFeesParams, ReflectParams, SetGuardianParams, SetPauseParams, SetUserPreferencesParams,
+};
+use sol_flex::state::{Config, CyclePhase, DistributionConfig, GlobalTokenPools, PauseFlags, UserPreferences};
+
+// One distribution with a funded fee vault and one registered holder, so that an
+// instruction from each pause scope can run.
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    token_mint: Pubkey,
+    fee_vault: Pubkey,
+    dev_token_account: Pubkey,
+    global_pools: Pubkey,
+    holder: (Pubkey, Pubkey), // Preferences, token account
+}
+
+impl Fixture {
+    fn new() -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_mint(token_mint, 6, 1_000_000);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.min_reflection_pool = 1;
+        config_state.min_reflection_per_account = 1;
+        config_state.holder_count = 1;
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        let fee_vault = Pubkey::new_unique();
+        let dev_token_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, bump);
+        distribution_state.reflection_rate = 10_000;
+        distribution_state.burn_rate = 0;
+        distribution_state.project_rate = 0;
+        distribution_state.dev_fee_rate = 0;
+        distribution_state.reflection_pool = 10_000;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_token_account(fee_vault, token_mint, distribution, 11_000);
+        ledger.add_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0);
+
+        let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+        ledger.add_state(
+            global_pools,
+            sol_flex::ID,
+            &GlobalTokenPools::new(authority, pools_bump),
+            8 + GlobalTokenPools::INIT_SPACE,
+        );
+
+        let owner = Pubkey::new_unique();
+        ledger.add_wallet(owner, 1_000_000_000);
+        let (preferences, _) =
+            Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, owner.as_ref()], &sol_flex::ID);
+        ledger.add_state(preferences, sol_flex::ID, &UserPreferences::new(owner, 0), 8 + UserPreferences::INIT_SPACE);
+        let token_account = Pubkey::new_unique();
+        ledger.add_token_account(token_account, token_mint, owner, 1_000);
+
+        Self {
+            ledger,
+            authority,
+            config,
+            distribution,
+            token_mint,
+            fee_vault,
+            dev_token_account,
+            global_pools,
+            holder: (preferences, token_account),
+        }
+    }
+
+    fn config(&self) -> Config {
+        self.ledger.state(&self.config)
+    }
+
+    fn set_pause(&mut self, signer: Pubkey, flags: PauseFlags) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::SetPause { config: self.config, proposal: None, authority: signer }
+                .to_account_metas(None),
+            data: sol_flex::instruction::SetPause { params: SetPauseParams { flags } }.data(),
+        })
+    }
+
+    fn set_guardian(&mut self, signer: Pubkey, guardian: Option<Pubkey>) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::SetGuardian { config: self.config, proposal: None, authority: signer }
+                .to_account_metas(None),
+            data: sol_flex::instruction::SetGuardian { params: SetGuardianParams { guardian } }.data(),
+        })
+    }
+
+    fn reflect(&mut self) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            dev_token_account: self.dev_token_account,
+            token_mint: self.token_mint,
+            global_pools: self.global_pools,
+            authority: self.authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(self.holder.0, false));
+        accounts.push(AccountMeta::new(self.holder.1, false));
+        let data =
+            sol_flex::instruction::Reflect { params: ReflectParams { pair_count: 1, swap_routes: Vec::new() } }.data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+
+    fn record_fees(&mut self) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::RecordFees {
+                config: self.config,
+                distribution_config: self.distribution,
+                fee_vault: self.fee_vault,
+                token_mint: self.token_mint,
+                authority: self.authority,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::RecordFees { params: RecordFeesParams { amount: 1_000 } }.data(),
+        })
+    }
+
+    // A new holder registers itself.
+    fn set_user_preferences(&mut self) -> std::result::Result<(), ProgramError> {
+        let user = Pubkey::new_unique();
+        self.ledger.add_wallet(user, 1_000_000_000);
+        let (user_preferences, _) =
+            Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, user.as_ref()], &sol_flex::ID);
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::SetUserPreferences {
+                config: self.config,
+                user_preferences,
+                user,
+                authority: user,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::SetUserPreferences {
+                params: SetUserPreferencesParams { preferred_pool_id: 0, custom_memo: String::new() },
+            }
+            .data(),
+        })
+    }
+
+    fn add_pool(&mut self) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::AddPool {
+                config: self.config,
+                global_pools: self.global_pools,
+                proposal: None,
+                authority: self.authority,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::AddPool {
+                params: AddPoolParams {
+                    pool_id: 1,
+                    token_mint: Pubkey::new_unique(),
+                    token_program: spl_token::ID,
+                    pool_address: Pubkey::new_unique(),
+                },
+            }
+            .data(),
+        })
+    }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn each_pause_flag_blocks_only_its_own_instructions() {
+    let scopes: [(PauseFlags, [bool; 4]); 6] = [
+        (PauseFlags::default(), [false, false, false, false]),
+        (PauseFlags { reflect: true, ..PauseFlags::default() }, [true, false, false, false]),
+        (PauseFlags { fees: true, ..PauseFlags::default() }, [false, true, false, false]),
+        (PauseFlags { preferences: true, ..PauseFlags::default() }, [false, false, true, false]),
+        (PauseFlags { pools: true, ..PauseFlags::default() }, [false, false, false, true]),
+        (PauseFlags { all: true, ..PauseFlags::default() }, [true, true, true, true]),
+    ];
+
+    for (flags, blocked) in scopes {
+        let mut fixture = Fixture::new();
+        fixture.set_pause(fixture.authority, flags).unwrap();
+        assert_eq!(fixture.config().pause_flags, flags);
+
+        let results = [fixture.reflect(), fixture.record_fees(), fixture.set_user_preferences(), fixture.add_pool()];
+        for (result, paused) in results.into_iter().zip(blocked) {
+            if paused {
+                assert_eq!(result.unwrap_err(), error(SolFlexError::ProgramPaused), "{:?}", flags);
+            } else {
+                result.unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn only_the_guardian_or_authority_changes_the_flags() {
+    let mut fixture = Fixture::new();
+    let guardian = Pubkey::new_unique();
+    let stranger = Pubkey::new_unique();
+    fixture.ledger.add_wallet(guardian, 1_000_000_000);
+    fixture.ledger.add_wallet(stranger, 1_000_000_000);
+    let reflect = PauseFlags { reflect: true, ..PauseFlags::default() };
+    let reflect_and_fees = PauseFlags { fees: true, ..reflect };
+
+    // Nobody but the authority pauses or appoints a guardian.
+    assert_eq!(fixture.set_pause(stranger, reflect).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(fixture.set_guardian(stranger, Some(stranger)).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(fixture.set_pause(guardian, reflect).unwrap_err(), error(SolFlexError::Unauthorized));
+    fixture.set_guardian(fixture.authority, Some(guardian)).unwrap();
+    assert_eq!(fixture.config().guardian, Some(guardian));
+
+    // The guardian can add flags, but neither clear nor swap them.
+    fixture.set_pause(guardian, reflect).unwrap();
+    fixture.set_pause(guardian, reflect_and_fees).unwrap();
+    assert_eq!(fixture.config().pause_flags, reflect_and_fees);
+    assert_eq!(fixture.set_pause(guardian, reflect).unwrap_err(), error(SolFlexError::Unauthorized));
+    let pools = PauseFlags { pools: true, ..PauseFlags::default() };
+    assert_eq!(fixture.set_pause(guardian, pools).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(fixture.set_pause(stranger, PauseFlags::default()).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(fixture.config().pause_flags, reflect_and_fees);
+
+    fixture.set_pause(fixture.authority, PauseFlags::default()).unwrap();
+    assert_eq!(fixture.config().pause_flags, PauseFlags::default());
+
+    // A removed guardian loses the right to pause.
+    fixture.set_guardian(fixture.authority, None).unwrap();
+    assert_eq!(fixture.set_pause(guardian, reflect).unwrap_err(), error(SolFlexError::Unauthorized));
+}
+
+#[test]
+fn the_guardian_pauses_without_a_proposal_once_a_multisig_governs() {
+    let mut fixture = Fixture::new();
+    let guardian = Pubkey::new_unique();
+    fixture.ledger.add_wallet(guardian, 1_000_000_000);
+    let mut config = fixture.config();
+    config.guardian = Some(guardian);
+    config.set_multisig(vec![fixture.authority, Pubkey::new_unique()], 2).unwrap();
+    fixture.ledger.add_state(fixture.config, sol_flex::ID, &config, 8 + Config::INIT_SPACE);
+
+    let all = PauseFlags { all: true, ..PauseFlags::default() };
+    fixture.set_pause(guardian, all).unwrap();
+    assert_eq!(fixture.config().pause_flags, all);
+
+    // Unpausing, by anyone, now takes an approved proposal.
+    assert_eq!(
+        fixture.set_pause(guardian, PauseFlags::default()).unwrap_err(),
+        error(SolFlexError::ProposalRequired)
+    );
+    assert_eq!(
+        fixture.set_pause(fixture.authority, PauseFlags::default()).unwrap_err(),
+        error(SolFlexError::ProposalRequired)
+    );
+    assert_eq!(fixture.config().pause_flags, all);
+}
+
+#[test]
+fn a_paused_cycle_resumes_where_it_stopped() {
+    let mut fixture = Fixture::new();
+    let (_, token_account) = fixture.holder;
+    fixture.reflect().unwrap();
+    let snapshot: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(snapshot.cycle_phase, CyclePhase::Payout);
+
+    // Pausing mid-cycle holds the payout sweep without touching the snapshot.
+    let reflect = PauseFlags { reflect: true, ..PauseFlags::default() };
+    fixture.set_pause(fixture.authority, reflect).unwrap();
+    assert_eq!(fixture.reflect().unwrap_err(), error(SolFlexError::ProgramPaused));
+    let paused: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!((paused.cycle_phase, paused.cycle_budget), (CyclePhase::Payout, snapshot.cycle_budget));
+    assert_eq!(fixture.ledger.token_amount(&token_account), 1_000);
+
+    fixture.set_pause(fixture.authority, PauseFlags::default()).unwrap();
+    fixture.reflect().unwrap();
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+    assert_eq!(fixture.ledger.token_amount(&token_account), 1_000 + snapshot.cycle_budget);
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+declare_id!("MockAmm111111111111111111111111111111111111");
+
+pub const MOCK_POOL_SEED: &[u8] = b"mock_pool";
+
+// Stands in for Jupiter in tests: swaps the base asset for the pool token at a fixed rate,
+// exposing the `swap(amount_in, minimum_amount_out)` adapter interface `reflect` calls.
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, rate_numerator: u64, rate_denominator: u64) -> Result<()> {
+        require!(rate_denominator > 0, MockAmmError::InvalidRate);
+        let pool = &mut ctx.accounts.pool;
+        pool.input_mint = ctx.accounts.input_mint.key();
+        pool.output_mint = ctx.accounts.output_mint.key();
+        pool.rate_numerator = rate_numerator;
+        pool.rate_denominator = rate_denominator;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let amount_out = (amount_in as u128 * pool.rate_numerator as u128 / pool.rate_denominator as u128) as u64;
+        require!(amount_out >= minimum_amount_out, MockAmmError::SlippageExceeded);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.input_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.source.to_account_info(),
+                    mint: ctx.accounts.input_mint.to_account_info(),
+                    to: ctx.accounts.pool_input_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount_in,
+            ctx.accounts.input_mint.decimals,
+        )?;
+
+        let output_mint = pool.output_mint;
+        let signer_seeds: &[&[u8]] = &[MOCK_POOL_SEED, output_mint.as_ref(), &[pool.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.output_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_output_vault.to_account_info(),
+                    mint: ctx.accounts.output_mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            amount_out,
+            ctx.accounts.output_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MockPool {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MockPool::INIT_SPACE,
+        seeds = [MOCK_POOL_SEED, output_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, MockPool>,
+    pub input_mint: InterfaceAccount<'info, Mint>,
+    pub output_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account order matches the sol-flex swap adapter interface (see src/swap_adapter.rs).
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, MockPool>,
+    #[account(mut, token::mint = pool.input_mint, token::authority = pool)]
+    pub pool_input_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = pool.output_mint, token::authority = pool)]
+    pub pool_output_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+    #[account(address = pool.input_mint)]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = pool.output_mint)]
+    pub output_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Invalid rate")]
+    InvalidRate,
+    #[msg("Swap output below minimum")]
+    SlippageExceeded,
+}
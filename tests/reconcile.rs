@@ -0,0 +1,168 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{ReconcileParams, ReflectParams};
+use sol_flex::state::{Config, CyclePhase, DistributionConfig, GlobalTokenPools, Role, UserPreferences};
+
+// A distribution tracking a 10,000 reflection pool, with one registered holder.
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    treasurer: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    token_mint: Pubkey,
+    fee_vault: Pubkey,
+    dev_token_account: Pubkey,
+    global_pools: Pubkey,
+    holder: (Pubkey, Pubkey), // Preferences, token account
+}
+
+impl Fixture {
+    fn new(vault: u64) -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let treasurer = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_wallet(treasurer, 1_000_000_000);
+        ledger.add_mint(token_mint, 6, 1_000_000);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.min_reflection_pool = 1;
+        config_state.min_reflection_per_account = 1;
+        config_state.holder_count = 1;
+        config_state.grant_role(treasurer, Role::Treasurer).unwrap();
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        let fee_vault = Pubkey::new_unique();
+        let dev_token_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, bump);
+        distribution_state.reflection_pool = 10_000;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_token_account(fee_vault, token_mint, distribution, vault);
+        ledger.add_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0);
+
+        let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+        ledger.add_state(
+            global_pools,
+            sol_flex::ID,
+            &GlobalTokenPools::new(authority, pools_bump),
+            8 + GlobalTokenPools::INIT_SPACE,
+        );
+
+        let owner = Pubkey::new_unique();
+        ledger.add_wallet(owner, 1_000_000_000);
+        let (preferences, _) =
+            Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, owner.as_ref()], &sol_flex::ID);
+        ledger.add_state(preferences, sol_flex::ID, &UserPreferences::new(owner, 0), 8 + UserPreferences::INIT_SPACE);
+        let token_account = Pubkey::new_unique();
+        ledger.add_token_account(token_account, token_mint, owner, 1_000);
+
+        Self {
+            ledger,
+            authority,
+            treasurer,
+            config,
+            distribution,
+            token_mint,
+            fee_vault,
+            dev_token_account,
+            global_pools,
+            holder: (preferences, token_account),
+        }
+    }
+
+    fn reconcile(&mut self, authority: Pubkey, credit_surplus: bool) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::Reconcile {
+                config: self.config,
+                distribution_config: self.distribution,
+                fee_vault: self.fee_vault,
+                authority,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::Reconcile { params: ReconcileParams { credit_surplus } }.data(),
+        })
+    }
+
+    fn reflect(&mut self) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            dev_token_account: self.dev_token_account,
+            token_mint: self.token_mint,
+            global_pools: self.global_pools,
+            authority: self.authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new(self.holder.0, false));
+        accounts.push(AccountMeta::new(self.holder.1, false));
+        let data =
+            sol_flex::instruction::Reflect { params: ReflectParams { pair_count: 1, swap_routes: Vec::new() } }.data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+
+    fn distribution(&self) -> DistributionConfig {
+        self.ledger.state(&self.distribution)
+    }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn a_vault_deficit_halts_reflect_until_a_treasurer_reconciles() {
+    let mut fixture = Fixture::new(9_000);
+    let stranger = Pubkey::new_unique();
+    fixture.ledger.add_wallet(stranger, 1_000_000_000);
+
+    // Anyone can flag the deficit, which stops reflect from paying out of a short vault.
+    fixture.reconcile(stranger, false).unwrap();
+    assert!(fixture.distribution().halted);
+    assert_eq!(fixture.reflect().unwrap_err(), error(SolFlexError::DistributionHalted));
+
+    // Topping the vault back up is not enough on its own: only a Treasurer lifts the halt.
+    fixture.ledger.add_token_account(fixture.fee_vault, fixture.token_mint, fixture.distribution, 10_000);
+    fixture.reconcile(stranger, false).unwrap();
+    assert!(fixture.distribution().halted);
+    fixture.reconcile(fixture.treasurer, false).unwrap();
+    assert!(!fixture.distribution().halted);
+
+    fixture.reflect().unwrap();
+    assert_eq!(fixture.distribution().cycle_phase, CyclePhase::Payout);
+}
+
+#[test]
+fn only_treasurers_credit_a_vault_surplus_to_reflection() {
+    let mut fixture = Fixture::new(10_500);
+    let stranger = Pubkey::new_unique();
+    fixture.ledger.add_wallet(stranger, 1_000_000_000);
+
+    assert_eq!(fixture.reconcile(stranger, true).unwrap_err(), error(SolFlexError::Unauthorized));
+    fixture.reconcile(stranger, false).unwrap();
+    assert_eq!(fixture.distribution().reflection_pool, 10_000);
+
+    fixture.reconcile(fixture.treasurer, true).unwrap();
+    let distribution = fixture.distribution();
+    assert_eq!(distribution.reflection_pool, 10_500);
+    assert_eq!(distribution.tracked_total().unwrap(), 10_500);
+    assert!(!distribution.halted);
+}
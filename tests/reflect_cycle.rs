@@ -0,0 +1,359 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{BanUserParams, ReflectParams};
+use sol_flex::state::{
+    Config, CrankReward, CyclePhase, DistributionConfig, DistributionMode, GlobalTokenPools, ReflectThrottle,
+    UserPreferences,
+};
+
+fn distribution(mode: DistributionMode, limit: u32, reflection_pool: u64) -> DistributionConfig {
+    let mut distribution = DistributionConfig::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        255,
+    );
+    distribution.distribution_mode = mode;
+    distribution.limit = limit;
+    distribution.reflection_pool = reflection_pool;
+    distribution
+}
+
+// Runs the cycle in batches of `limit` and returns each holder's payout.
+fn run_cycle(distribution: &mut DistributionConfig, balances: &[u64]) -> Vec<u64> {
+    let mut payouts = Vec::with_capacity(balances.len());
+    for batch in balances.chunks(distribution.limit as usize) {
+        let mut allocated = 0;
+        for balance in batch {
+            let payout = distribution.cycle_payout(*balance, allocated).unwrap();
+            allocated += payout;
+            payouts.push(payout);
+        }
+        distribution.distribute_reflection(allocated).unwrap();
+        distribution
+            .record_cycle_batch(allocated, batch.len() as u32, batch.iter().sum(), 0)
+            .unwrap();
+    }
+    payouts
+}
+
+#[test]
+fn equal_cycle_pays_every_holder_beyond_the_batch_limit() {
+    common::setup();
+    let mut distribution = distribution(DistributionMode::Equal, 3, 1_000);
+
+    // Seven registered holders, three per batch: the first cycle must budget for all seven.
+    distribution.start_cycle(1_000, 1_000_000, 7).unwrap();
+    assert_eq!(distribution.cycle_rate, 142);
+    assert_eq!(distribution.cycle_budget, 994);
+    assert_eq!(distribution.dust_pool, 6);
+
+    let payouts = run_cycle(&mut distribution, &[0; 7]);
+    assert_eq!(payouts, vec![142; 7]);
+    assert_eq!(distribution.cycle_distributed, distribution.cycle_budget);
+    assert_eq!(distribution.reflection_pool, 0);
+}
+
+#[test]
+fn proportional_cycle_pays_every_holder_beyond_the_batch_limit() {
+    common::setup();
+    let mut distribution = distribution(DistributionMode::Proportional, 2, 10_000);
+    let balances = [400_000, 100_000, 250_000, 150_000, 100_000];
+
+    distribution.start_cycle(10_000, 1_000_000, balances.len() as u64).unwrap();
+
+    let payouts = run_cycle(&mut distribution, &balances);
+    assert_eq!(payouts, vec![4_000, 1_000, 2_500, 1_500, 1_000]);
+    assert!(distribution.cycle_distributed <= distribution.cycle_budget);
+}
+
+#[test]
+fn equal_cycle_requires_registered_holders() {
+    common::setup();
+    let mut distribution = distribution(DistributionMode::Equal, 3, 1_000);
+
+    assert!(distribution.start_cycle(1_000, 1_000_000, 0).is_err());
+}
+
+#[test]
+fn crank_rewards_are_capped_per_cycle() {
+    common::setup();
+    let mut distribution = distribution(DistributionMode::Proportional, 2, 100_000);
+    distribution.crank_reward = CrankReward::BasisPoints(1000);
+    distribution.max_crank_reward_bps = 100;
+    distribution.start_cycle(50_000, 1_000_000, 4).unwrap();
+
+    // 10% of each batch, but at most 1% of the 50,000 budget across the cycle.
+    let first = distribution.crank_reward_amount(4_000).unwrap();
+    assert_eq!(first, 400);
+    distribution.pay_crank_reward(first).unwrap();
+    let second = distribution.crank_reward_amount(4_000).unwrap();
+    assert_eq!(second, 100);
+    distribution.pay_crank_reward(second).unwrap();
+    assert_eq!(distribution.crank_reward_amount(4_000).unwrap(), 0);
+
+    // A new cycle starts a new allowance.
+    distribution.start_cycle(50_000, 1_000_000, 4).unwrap();
+    assert_eq!(distribution.crank_reward_amount(4_000).unwrap(), 400);
+}
+
+#[test]
+fn permissionless_crank_requires_proportional_mode_without_flat_rewards() {
+    common::setup();
+    let mut distribution = distribution(DistributionMode::Proportional, 2, 0);
+    distribution.permissionless_crank = true;
+    distribution.crank_reward = CrankReward::BasisPoints(10);
+    assert!(distribution.validate_rates().is_ok());
+
+    distribution.crank_reward = CrankReward::Flat(1);
+    assert!(distribution.validate_rates().is_err());
+
+    distribution.crank_reward = CrankReward::None;
+    distribution.distribution_mode = DistributionMode::Equal;
+    assert!(distribution.validate_rates().is_err());
+}
+
+// `mode` distribution of a 1,000,000 supply mint with `balances.len()` registered holders,
+// cranked by the authority in batches of two.
+struct Sweep {
+    ledger: Ledger,
+    authority: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    fee_vault: Pubkey,
+    dev_token_account: Pubkey,
+    token_mint: Pubkey,
+    global_pools: Pubkey,
+    holders: Vec<(Pubkey, Pubkey, Pubkey)>, // Owner, preferences, token account
+}
+
+impl Sweep {
+    fn new(mode: DistributionMode, balances: &[u64]) -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_mint(token_mint, 6, 1_000_000);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.min_reflection_pool = 1;
+        config_state.min_reflection_per_account = 1;
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        let fee_vault = Pubkey::new_unique();
+        let dev_token_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, bump);
+        distribution_state.distribution_mode = mode;
+        distribution_state.throttle = ReflectThrottle::DrainAll;
+        distribution_state.limit = 2;
+        distribution_state.reflection_pool = 10_000;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_token_account(fee_vault, token_mint, distribution, 10_000);
+        ledger.add_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0);
+
+        let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+        ledger.add_state(
+            global_pools,
+            sol_flex::ID,
+            &GlobalTokenPools::new(authority, pools_bump),
+            8 + GlobalTokenPools::INIT_SPACE,
+        );
+
+        let mut sweep = Self {
+            ledger,
+            authority,
+            config,
+            distribution,
+            fee_vault,
+            dev_token_account,
+            token_mint,
+            global_pools,
+            holders: Vec::new(),
+        };
+        for balance in balances {
+            sweep.register(*balance);
+        }
+        sweep
+    }
+
+    // Adds a holder at the next holder_index, as set_user_preferences would.
+    fn register(&mut self, balance: u64) {
+        let owner = Pubkey::new_unique();
+        self.ledger.add_wallet(owner, 1_000_000_000);
+        let (preferences, _) =
+            Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, owner.as_ref()], &sol_flex::ID);
+        let mut config: Config = self.ledger.state(&self.config);
+        self.ledger.add_state(
+            preferences,
+            sol_flex::ID,
+            &UserPreferences::new(owner, config.holder_count),
+            8 + UserPreferences::INIT_SPACE,
+        );
+        config.holder_count += 1;
+        self.ledger.add_state(self.config, sol_flex::ID, &config, 8 + Config::INIT_SPACE);
+        let token_account = Pubkey::new_unique();
+        self.ledger.add_token_account(token_account, self.token_mint, owner, balance);
+        self.holders.push((owner, preferences, token_account));
+    }
+
+    fn set_balance(&mut self, holder: usize, balance: u64) {
+        let (owner, _, token_account) = self.holders[holder];
+        self.ledger.add_token_account(token_account, self.token_mint, owner, balance);
+    }
+
+    fn reflect(&mut self, holders: &[usize]) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            dev_token_account: self.dev_token_account,
+            token_mint: self.token_mint,
+            global_pools: self.global_pools,
+            authority: self.authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        for holder in holders {
+            let (_, preferences, token_account) = self.holders[*holder];
+            accounts.push(AccountMeta::new(preferences, false));
+            accounts.push(AccountMeta::new(token_account, false));
+        }
+        let data = sol_flex::instruction::Reflect {
+            params: ReflectParams { pair_count: holders.len() as u16, swap_routes: Vec::new() },
+        }
+        .data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+
+    // The holder bans itself, releasing its place in the distribution's running cycle.
+    fn ban(&mut self, holder: usize) {
+        let (owner, preferences, _) = self.holders[holder];
+        let ban = Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::BanUser {
+                config: self.config,
+                user_preferences: preferences,
+                distribution_config: Some(self.distribution),
+                holder_rewards: None,
+                authority: owner,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::BanUser { params: BanUserParams { user_to_ban: owner, ban_status: true } }
+                .data(),
+        };
+        self.ledger.process(&ban).unwrap();
+    }
+
+    fn distribution(&self) -> DistributionConfig {
+        self.ledger.state(&self.distribution)
+    }
+}
+
+#[test]
+fn sweeps_visit_every_holder_in_order_and_pay_at_most_the_snapshot() {
+    let mut sweep = Sweep::new(DistributionMode::Proportional, &[400_000, 100_000, 100_000]);
+    let out_of_order = anchor_lang::error::Error::from(SolFlexError::HolderOutOfOrder).into();
+
+    // A batch cannot start past the cursor, nor leave a gap.
+    assert_eq!(sweep.reflect(&[1, 2]).unwrap_err(), out_of_order);
+    assert_eq!(sweep.reflect(&[0, 2]).unwrap_err(), out_of_order);
+    assert_eq!(sweep.distribution().cycle_phase, CyclePhase::Idle);
+
+    // Snapshot sweep in two batches; nothing is paid.
+    sweep.reflect(&[0, 1]).unwrap();
+    assert_eq!(sweep.distribution().cycle_phase, CyclePhase::Snapshot);
+    assert_eq!(sweep.distribution().holder_cursor, 2);
+    sweep.reflect(&[2]).unwrap();
+    let distribution = sweep.distribution();
+    assert_eq!(distribution.cycle_phase, CyclePhase::Payout);
+    assert_eq!((distribution.holder_cursor, distribution.cycle_holders), (0, 3));
+    assert_eq!(sweep.ledger.token_amount(&sweep.fee_vault), 10_000);
+
+    // A holder registered mid-cycle waits for the next one, and the payout sweep
+    // cannot skip ahead either.
+    sweep.register(500_000);
+    assert_eq!(sweep.reflect(&[1]).unwrap_err(), out_of_order);
+
+    // Holder 0 moves most of its balance to holder 2 between the sweeps: each is paid on
+    // the lower of its snapshot and current balance, so the moved tokens earn once.
+    sweep.set_balance(0, 100_000);
+    sweep.set_balance(2, 400_000);
+    sweep.reflect(&[0, 1]).unwrap();
+    sweep.reflect(&[2, 3]).unwrap();
+    let paid: Vec<u64> = sweep.holders.iter().map(|(_, _, account)| sweep.ledger.token_amount(account)).collect();
+    assert_eq!(paid, vec![101_000, 101_000, 401_000, 500_000]);
+
+    let distribution = sweep.distribution();
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+    assert_eq!(distribution.last_cycle_recipients, 3);
+    assert_eq!(distribution.cycle_distributed, 3_000);
+    assert_eq!(distribution.reflection_pool, 7_000);
+}
+
+#[test]
+fn equal_cycles_divide_the_budget_among_funded_unbanned_holders() {
+    // Holder 1 registered an empty wallet; holder 3 bans itself after being counted.
+    let mut sweep = Sweep::new(DistributionMode::Equal, &[1_000, 0, 500, 200, 300]);
+
+    sweep.reflect(&[0, 1]).unwrap();
+    sweep.reflect(&[2, 3]).unwrap();
+    assert_eq!(sweep.distribution().cycle_eligible, 3);
+    sweep.ban(3);
+    assert_eq!(sweep.distribution().cycle_eligible, 2);
+    sweep.reflect(&[4]).unwrap();
+
+    // 10,000 over the three remaining holders; the remainder is dust.
+    let distribution = sweep.distribution();
+    assert_eq!(distribution.cycle_phase, CyclePhase::Payout);
+    assert_eq!((distribution.cycle_eligible, distribution.cycle_rate), (3, 3_333));
+    assert_eq!(distribution.dust_pool, 1);
+
+    sweep.reflect(&[0, 1]).unwrap();
+    sweep.reflect(&[2, 3]).unwrap();
+    sweep.reflect(&[4]).unwrap();
+    let paid: Vec<u64> = sweep.holders.iter().map(|(_, _, account)| sweep.ledger.token_amount(account)).collect();
+    assert_eq!(paid, vec![4_333, 0, 3_833, 200, 3_633]);
+    assert_eq!(sweep.distribution().cycle_phase, CyclePhase::Idle);
+    assert_eq!(sweep.distribution().reflection_pool, 0);
+}
+
+#[test]
+fn frozen_accounts_are_held_while_the_rest_of_the_batch_is_paid() {
+    let mut sweep = Sweep::new(DistributionMode::Equal, &[1_000, 1_000, 1_000]);
+    sweep.reflect(&[0, 1]).unwrap();
+    sweep.reflect(&[2]).unwrap();
+
+    // Holder 1's account is frozen after the snapshot: its share is held for claim_pending
+    // rather than failing the transfer and with it the batch.
+    let (_, preferences, token_account) = sweep.holders[1];
+    sweep.ledger.freeze_token_account(token_account);
+    sweep.reflect(&[0, 1]).unwrap();
+    sweep.reflect(&[2]).unwrap();
+
+    let paid: Vec<u64> = sweep.holders.iter().map(|(_, _, account)| sweep.ledger.token_amount(account)).collect();
+    assert_eq!(paid, vec![4_333, 1_000, 4_333]);
+    let held: UserPreferences = sweep.ledger.state(&preferences);
+    assert_eq!(held.pending_reflections.len(), 1);
+    assert_eq!(held.pending_reflections[0].distribution, sweep.distribution);
+    assert_eq!(held.pending_reflections[0].amount, 3_333);
+
+    let distribution = sweep.distribution();
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+    assert_eq!(distribution.pending_pool, 3_333);
+    assert_eq!(sweep.ledger.token_amount(&sweep.fee_vault), 3_334);
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use common::runtime::Ledger;
+use mock_amm::{MockAmmError, MockPool, MOCK_POOL_SEED};
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{ReflectParams, SwapRoute};
+use sol_flex::state::{Config, CyclePhase, DistributionConfig, DistributionMode, GlobalTokenPools, ReflectThrottle, TokenPool, UserPreferences};
+use sol_flex::swap_adapter::SWAP_RATE_PRECISION;
+
+const POOL_ID: u64 = 1;
+const REFLECTION: u64 = 10_000;
+const HOLDING: u64 = 1_000;
+
+// One holder preferring pool 1, whose mock AMM pays 3 pool tokens per 2 base tokens.
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    token_mint: Pubkey,
+    pool_mint: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    fee_vault: Pubkey,
+    dev_token_account: Pubkey,
+    global_pools: Pubkey,
+    preferences: Pubkey,
+    holder_base_account: Pubkey,
+    holder_pool_account: Pubkey,
+    amm_pool: Pubkey,
+    amm_input_vault: Pubkey,
+    amm_output_vault: Pubkey,
+    intermediate: Pubkey,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let pool_mint = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_mint(token_mint, 6, 1_000_000);
+        ledger.add_mint(pool_mint, 6, 1_000_000);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.min_reflection_pool = 1;
+        config_state.min_reflection_per_account = 1;
+        config_state.holder_count = 1;
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, distribution_bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        let fee_vault = Pubkey::new_unique();
+        let dev_token_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, distribution_bump);
+        distribution_state.distribution_mode = DistributionMode::Equal;
+        distribution_state.throttle = ReflectThrottle::DrainAll;
+        distribution_state.reflection_pool = REFLECTION;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_token_account(fee_vault, token_mint, distribution, REFLECTION);
+        ledger.add_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0);
+
+        let (amm_pool, amm_bump) = Pubkey::find_program_address(&[MOCK_POOL_SEED, pool_mint.as_ref()], &mock_amm::ID);
+        let pool_state = MockPool {
+            input_mint: token_mint,
+            output_mint: pool_mint,
+            rate_numerator: 3,
+            rate_denominator: 2,
+            bump: amm_bump,
+        };
+        ledger.add_state(amm_pool, mock_amm::ID, &pool_state, 8 + MockPool::INIT_SPACE);
+        let amm_input_vault = Pubkey::new_unique();
+        let amm_output_vault = Pubkey::new_unique();
+        ledger.add_token_account(amm_input_vault, token_mint, amm_pool, 0);
+        ledger.add_token_account(amm_output_vault, pool_mint, amm_pool, 500_000);
+        let intermediate = Pubkey::new_unique();
+        ledger.add_token_account(intermediate, pool_mint, distribution, 0);
+
+        let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+        let mut pools_state = GlobalTokenPools::new(authority, pools_bump);
+        pools_state
+            .add_pool(TokenPool::new(POOL_ID, pool_mint, spl_token::ID, amm_pool))
+            .unwrap();
+        ledger.add_state(global_pools, sol_flex::ID, &pools_state, 8 + GlobalTokenPools::INIT_SPACE);
+
+        let (preferences, _) =
+            Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, holder.as_ref()], &sol_flex::ID);
+        let mut preferences_state = UserPreferences::new(holder, 0);
+        preferences_state.preferred_pool_id = POOL_ID;
+        ledger.add_state(preferences, sol_flex::ID, &preferences_state, 8 + UserPreferences::INIT_SPACE);
+        let holder_base_account = Pubkey::new_unique();
+        ledger.add_token_account(holder_base_account, token_mint, holder, HOLDING);
+        let holder_pool_account = get_associated_token_address_with_program_id(&holder, &pool_mint, &spl_token::ID);
+        ledger.add_token_account(holder_pool_account, pool_mint, holder, 0);
+
+        let mut fixture = Self {
+            ledger,
+            authority,
+            token_mint,
+            pool_mint,
+            config,
+            distribution,
+            fee_vault,
+            dev_token_account,
+            global_pools,
+            preferences,
+            holder_base_account,
+            holder_pool_account,
+            amm_pool,
+            amm_input_vault,
+            amm_output_vault,
+            intermediate,
+        };
+        // Every test starts in the payout sweep; the snapshot sweep ignores the routes.
+        fixture.reflect(0).unwrap();
+        fixture
+    }
+
+    fn reflect(&mut self, min_out_rate: u64) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            dev_token_account: self.dev_token_account,
+            token_mint: self.token_mint,
+            global_pools: self.global_pools,
+            authority: self.authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend([
+            AccountMeta::new(self.preferences, false),
+            AccountMeta::new(self.holder_base_account, false),
+            AccountMeta::new(self.amm_pool, false),
+            AccountMeta::new(self.amm_input_vault, false),
+            AccountMeta::new(self.amm_output_vault, false),
+            AccountMeta::new_readonly(mock_amm::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(self.pool_mint, false),
+            AccountMeta::new(self.intermediate, false),
+            AccountMeta::new(self.holder_pool_account, false),
+        ]);
+        let data = sol_flex::instruction::Reflect {
+            params: ReflectParams {
+                pair_count: 1,
+                swap_routes: vec![SwapRoute { pool_id: POOL_ID, min_out_rate }],
+            },
+        }
+        .data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+
+    fn registered_pool(&mut self, pool: TokenPool) {
+        let mut pools: GlobalTokenPools = self.ledger.state(&self.global_pools);
+        pools.remove_pool(POOL_ID).unwrap();
+        pools.add_pool(pool).unwrap();
+        self.ledger
+            .add_state(self.global_pools, sol_flex::ID, &pools, 8 + GlobalTokenPools::INIT_SPACE);
+    }
+}
+
+fn error(error: impl Into<anchor_lang::error::Error>) -> ProgramError {
+    error.into().into()
+}
+
+#[test]
+fn swap_pays_the_preferred_token_at_the_quoted_minimum() {
+    let mut fixture = Fixture::new();
+
+    // The quote equals the AMM rate exactly, so the minimum out is met without slack.
+    fixture.reflect(SWAP_RATE_PRECISION * 3 / 2).unwrap();
+
+    assert_eq!(fixture.ledger.token_amount(&fixture.holder_pool_account), REFLECTION * 3 / 2);
+    assert_eq!(fixture.ledger.token_amount(&fixture.holder_base_account), HOLDING);
+    assert_eq!(fixture.ledger.token_amount(&fixture.amm_input_vault), REFLECTION);
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 0);
+    assert_eq!(fixture.ledger.token_amount(&fixture.intermediate), 0);
+
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.reflection_pool, 0);
+    assert_eq!(distribution.cycle_distributed, REFLECTION);
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+    assert_eq!(distribution.last_cycle_recipients, 1);
+}
+
+#[test]
+fn swap_below_the_quoted_minimum_fails_the_batch() {
+    let mut fixture = Fixture::new();
+
+    // One part in a thousand above what the AMM pays.
+    let min_out_rate = SWAP_RATE_PRECISION * 3 / 2 + SWAP_RATE_PRECISION / 1_000;
+    assert_eq!(fixture.reflect(min_out_rate).unwrap_err(), error(MockAmmError::SlippageExceeded));
+
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), REFLECTION);
+    assert_eq!(fixture.ledger.token_amount(&fixture.holder_pool_account), 0);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.reflection_pool, REFLECTION);
+    assert_eq!(distribution.cycle_phase, CyclePhase::Payout);
+    assert_eq!(distribution.holder_cursor, 0);
+}
+
+#[test]
+fn route_must_match_the_registered_pool_and_mint() {
+    let mut fixture = Fixture::new();
+    let pool_mint = fixture.pool_mint;
+
+    // Registered for another output mint than the route's.
+    fixture.registered_pool(TokenPool::new(POOL_ID, Pubkey::new_unique(), spl_token::ID, fixture.amm_pool));
+    assert_eq!(
+        fixture.reflect(SWAP_RATE_PRECISION).unwrap_err(),
+        error(SolFlexError::InvalidSwapRoute)
+    );
+
+    // Registered for another pool address than the route's.
+    fixture.registered_pool(TokenPool::new(POOL_ID, pool_mint, spl_token::ID, Pubkey::new_unique()));
+    assert_eq!(
+        fixture.reflect(SWAP_RATE_PRECISION).unwrap_err(),
+        error(SolFlexError::InvalidSwapRoute)
+    );
+
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), REFLECTION);
+}
+
+#[test]
+fn frozen_pool_token_account_is_paid_in_the_base_asset() {
+    let mut fixture = Fixture::new();
+    fixture.ledger.freeze_token_account(fixture.holder_pool_account);
+
+    // The pool's transfer would fail the batch, so the holder falls back to the base asset.
+    fixture.reflect(SWAP_RATE_PRECISION * 3 / 2).unwrap();
+
+    assert_eq!(fixture.ledger.token_amount(&fixture.holder_base_account), HOLDING + REFLECTION);
+    assert_eq!(fixture.ledger.token_amount(&fixture.holder_pool_account), 0);
+    assert_eq!(fixture.ledger.token_amount(&fixture.amm_input_vault), 0);
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 0);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+}
@@ -0,0 +1,267 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::native_mint;
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{QueueConfigChangeParams, ReflectParams};
+use sol_flex::state::{
+    Config, ConfigChange, CyclePhase, DistributionConfig, DistributionMode, GlobalTokenPools, PendingConfigChange,
+    ReflectThrottle, RewardAsset, RewardAssetKind, UserPreferences,
+};
+
+const VAULT: u64 = 500;
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn removal_is_timelocked_and_sweeps_only_to_the_treasury() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let admin = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    let reward_mint = Pubkey::new_unique();
+    ledger.add_wallet(admin, 1_000_000_000);
+    ledger.add_mint(token_mint, 6, 1_000_000);
+    ledger.add_mint(reward_mint, 6, 1_000_000);
+
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    let mut config_state = Config::new(admin, config_bump);
+    config_state.timelock_delay = 3_600;
+    ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+    let (distribution, distribution_bump) =
+        Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+    let project_token_account = Pubkey::new_unique();
+    ledger.add_token_account(project_token_account, token_mint, treasury, 0);
+    let mut distribution_state = DistributionConfig::new(
+        token_mint,
+        Pubkey::new_unique(),
+        project_token_account,
+        Pubkey::new_unique(),
+        distribution_bump,
+    );
+    distribution_state.reward_asset_count = 1;
+    ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+
+    let (reward_asset, reward_asset_bump) = Pubkey::find_program_address(
+        &[RewardAsset::SEED_PREFIX, distribution.as_ref(), reward_mint.as_ref()],
+        &sol_flex::ID,
+    );
+    let reward_vault = Pubkey::new_unique();
+    ledger.add_token_account(reward_vault, reward_mint, distribution, VAULT);
+    let mut reward_asset_state = RewardAsset::new(
+        distribution,
+        RewardAssetKind::Token,
+        reward_mint,
+        reward_vault,
+        spl_token::ID,
+        1,
+        ReflectThrottle::BasisPoints(100),
+        reward_asset_bump,
+    );
+    reward_asset_state.credit(VAULT).unwrap();
+    ledger.add_state(reward_asset, sol_flex::ID, &reward_asset_state, 8 + RewardAsset::INIT_SPACE);
+
+    let treasury_account = Pubkey::new_unique();
+    ledger.add_token_account(treasury_account, reward_mint, treasury, 0);
+    let admin_account = Pubkey::new_unique();
+    ledger.add_token_account(admin_account, reward_mint, admin, 0);
+
+    let (pending_change, _) =
+        Pubkey::find_program_address(&[PendingConfigChange::SEED_PREFIX, 0u64.to_le_bytes().as_ref()], &sol_flex::ID);
+    ledger
+        .process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::QueueConfigChange {
+                config,
+                distribution_config: Some(distribution),
+                pending_change,
+                proposal: None,
+                authority: admin,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::QueueConfigChange {
+                params: QueueConfigChangeParams { change: ConfigChange::RemoveRewardAsset(reward_mint) },
+            }
+            .data(),
+        })
+        .unwrap();
+
+    let remove = |sweep_destination: Pubkey| Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::RemoveRewardAsset {
+            config,
+            distribution_config: distribution,
+            reward_asset,
+            pending_change,
+            queued_by: admin,
+            project_token_account,
+            reward_vault: Some(reward_vault),
+            reward_mint: Some(reward_mint),
+            sweep_destination: Some(sweep_destination),
+            treasury: None,
+            executor: admin,
+            token_program: Some(spl_token::ID),
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::RemoveRewardAsset {}.data(),
+    };
+
+    // The Admin cannot redirect the vault to an account of their own.
+    assert_eq!(
+        ledger.process(&remove(admin_account)).unwrap_err(),
+        error(SolFlexError::InvalidTokenAccount)
+    );
+    assert_eq!(
+        ledger.process(&remove(treasury_account)).unwrap_err(),
+        error(SolFlexError::TimelockNotElapsed)
+    );
+
+    // Wait out the delay without moving the shared clock.
+    let mut pending_state: PendingConfigChange = ledger.state(&pending_change);
+    pending_state.executable_at = common::now();
+    ledger.add_state(pending_change, sol_flex::ID, &pending_state, 8 + PendingConfigChange::INIT_SPACE);
+    ledger.process(&remove(treasury_account)).unwrap();
+
+    assert_eq!(ledger.token_amount(&reward_vault), 0);
+    assert_eq!(ledger.token_amount(&treasury_account), VAULT);
+    assert_eq!(ledger.token_amount(&admin_account), 0);
+    assert_eq!(ledger.lamports(&reward_asset), 0);
+    assert_eq!(ledger.lamports(&pending_change), 0);
+    let distribution_state: DistributionConfig = ledger.state(&distribution);
+    assert_eq!(distribution_state.reward_asset_count, 0);
+}
+
+#[test]
+fn lamport_income_alone_is_reflected_to_holder_wallets() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let authority = Pubkey::new_unique();
+    let token_mint = Pubkey::new_unique();
+    ledger.add_wallet(authority, 1_000_000_000);
+    ledger.add_mint(token_mint, 6, 1_000_000);
+
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    let mut config_state = Config::new(authority, config_bump);
+    config_state.min_reflection_pool = 1_000;
+    config_state.min_reflection_per_account = 100;
+    config_state.holder_count = 2;
+    ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+    // The base pool is below the minimum; all the income so far arrived as lamports.
+    let (distribution, distribution_bump) =
+        Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+    let fee_vault = Pubkey::new_unique();
+    let dev_token_account = Pubkey::new_unique();
+    let mut distribution_state =
+        DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, distribution_bump);
+    distribution_state.distribution_mode = DistributionMode::Equal;
+    distribution_state.reflection_pool = 10;
+    distribution_state.reward_asset_count = 1;
+    ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+    ledger.add_token_account(fee_vault, token_mint, distribution, 10);
+    ledger.add_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0);
+
+    let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+    ledger.add_state(
+        global_pools,
+        sol_flex::ID,
+        &GlobalTokenPools::new(authority, pools_bump),
+        8 + GlobalTokenPools::INIT_SPACE,
+    );
+
+    let (reward_asset, reward_asset_bump) = Pubkey::find_program_address(
+        &[RewardAsset::SEED_PREFIX, distribution.as_ref(), native_mint::ID.as_ref()],
+        &sol_flex::ID,
+    );
+    let mut reward_asset_state = RewardAsset::new(
+        distribution,
+        RewardAssetKind::NativeSol,
+        native_mint::ID,
+        reward_asset,
+        anchor_lang::system_program::ID,
+        1,
+        ReflectThrottle::DrainAll,
+        reward_asset_bump,
+    );
+    reward_asset_state.credit(VAULT).unwrap();
+    ledger.add_state(reward_asset, sol_flex::ID, &reward_asset_state, 8 + RewardAsset::INIT_SPACE);
+
+    let holders: Vec<(Pubkey, Pubkey, Pubkey)> = (0..2)
+        .map(|index| {
+            let owner = Pubkey::new_unique();
+            ledger.add_wallet(owner, 1_000_000_000);
+            let (preferences, _) =
+                Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, owner.as_ref()], &sol_flex::ID);
+            ledger.add_state(
+                preferences,
+                sol_flex::ID,
+                &UserPreferences::new(owner, index),
+                8 + UserPreferences::INIT_SPACE,
+            );
+            let token_account = Pubkey::new_unique();
+            ledger.add_token_account(token_account, token_mint, owner, 1_000);
+            (owner, preferences, token_account)
+        })
+        .collect();
+
+    let reflect = |basket: bool| {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config,
+            distribution_config: distribution,
+            fee_vault,
+            dev_token_account,
+            token_mint,
+            global_pools,
+            authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        for (_, preferences, token_account) in &holders {
+            accounts.push(AccountMeta::new(*preferences, false));
+            accounts.push(AccountMeta::new(*token_account, false));
+        }
+        if basket {
+            accounts.extend([
+                AccountMeta::new(reward_asset, false),
+                AccountMeta::new(reward_asset, false),
+                AccountMeta::new_readonly(native_mint::ID, false),
+                AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+            ]);
+            accounts.extend(holders.iter().map(|(owner, ..)| AccountMeta::new(*owner, false)));
+        }
+        let data = sol_flex::instruction::Reflect {
+            params: ReflectParams { pair_count: holders.len() as u16, swap_routes: Vec::new() },
+        }
+        .data();
+        Instruction { program_id: sol_flex::ID, accounts, data }
+    };
+
+    ledger.process(&reflect(false)).unwrap();
+    let distribution_state: DistributionConfig = ledger.state(&distribution);
+    assert_eq!(distribution_state.cycle_phase, CyclePhase::Payout);
+    assert_eq!(distribution_state.cycle_budget, 0);
+
+    ledger.process(&reflect(true)).unwrap();
+    for (owner, _, token_account) in &holders {
+        assert_eq!(ledger.lamports(owner), 1_000_000_000 + VAULT / 2);
+        assert_eq!(ledger.token_amount(token_account), 1_000);
+    }
+    let reward_asset_state: RewardAsset = ledger.state(&reward_asset);
+    assert_eq!(reward_asset_state.reflection_pool, 0);
+    let distribution_state: DistributionConfig = ledger.state(&distribution);
+    assert_eq!(distribution_state.cycle_phase, CyclePhase::Idle);
+    assert_eq!(distribution_state.reflection_pool, 10);
+    assert_eq!(ledger.token_amount(&fee_vault), 10);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{GrantRoleParams, RevokeRoleParams, UpdateConfigParams};
+use sol_flex::state::{Config, Role};
+
+const ROLES: [Role; 6] =
+    [Role::Admin, Role::Cranker, Role::FeeRecorder, Role::BlocklistManager, Role::PoolManager, Role::Treasurer];
+
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    operator: Pubkey,
+    config: Pubkey,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+        ledger.add_wallet(operator, 1_000_000_000);
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        ledger.add_state(config, sol_flex::ID, &Config::new(authority, config_bump), 8 + Config::INIT_SPACE);
+        Self { ledger, authority, operator, config }
+    }
+
+    fn config(&self) -> Config {
+        self.ledger.state(&self.config)
+    }
+
+    fn grant_role(
+        &mut self,
+        authority: Pubkey,
+        account: Pubkey,
+        role: Role,
+    ) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::GrantRole { config: self.config, proposal: None, authority }
+                .to_account_metas(None),
+            data: sol_flex::instruction::GrantRole { params: GrantRoleParams { account, role } }.data(),
+        })
+    }
+
+    fn revoke_role(
+        &mut self,
+        authority: Pubkey,
+        account: Pubkey,
+        role: Role,
+    ) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::RevokeRole { config: self.config, proposal: None, authority }
+                .to_account_metas(None),
+            data: sol_flex::instruction::RevokeRole { params: RevokeRoleParams { account, role } }.data(),
+        })
+    }
+
+    fn update_config(&mut self, authority: Pubkey) -> std::result::Result<(), ProgramError> {
+        self.ledger.process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::UpdateConfig { config: self.config, proposal: None, authority }
+                .to_account_metas(None),
+            data: sol_flex::instruction::UpdateConfig {
+                params: UpdateConfigParams { min_reflection_pool: 5_000, min_reflection_per_account: 5 },
+            }
+            .data(),
+        })
+    }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn roles_are_held_only_by_the_accounts_granted_them() {
+    common::setup();
+    let authority = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let mut config = Config::new(authority, 255);
+
+    for role in ROLES {
+        assert!(!config.has_role(operator, role));
+    }
+    config.grant_role(operator, Role::Cranker).unwrap();
+    config.grant_role(operator, Role::Treasurer).unwrap();
+    for role in ROLES {
+        assert_eq!(config.has_role(operator, role), matches!(role, Role::Cranker | Role::Treasurer));
+    }
+    assert!(!config.has_role(Pubkey::new_unique(), Role::Cranker));
+
+    assert_eq!(config.grant_role(operator, Role::Cranker).unwrap_err(), SolFlexError::RoleAlreadyGranted.into());
+    config.revoke_role(operator, Role::Cranker).unwrap();
+    assert!(!config.has_role(operator, Role::Cranker));
+    assert!(config.has_role(operator, Role::Treasurer));
+    assert_eq!(config.revoke_role(operator, Role::Cranker).unwrap_err(), SolFlexError::RoleNotGranted.into());
+}
+
+#[test]
+fn the_authority_holds_every_role_only_without_a_multisig() {
+    common::setup();
+    let authority = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let mut config = Config::new(authority, 255);
+    config.grant_role(operator, Role::FeeRecorder).unwrap();
+    for role in ROLES {
+        assert!(config.has_role(authority, role));
+    }
+
+    // Once a multisig governs, the authority keeps only what was granted to it explicitly.
+    config.set_multisig(vec![authority, Pubkey::new_unique()], 2).unwrap();
+    for role in ROLES {
+        assert!(!config.has_role(authority, role));
+    }
+    assert!(config.has_role(operator, Role::FeeRecorder));
+    config.grant_role(authority, Role::Cranker).unwrap();
+    assert!(config.has_role(authority, Role::Cranker));
+    assert!(!config.has_role(authority, Role::Admin));
+
+    config.set_multisig(Vec::new(), 0).unwrap();
+    for role in ROLES {
+        assert!(config.has_role(authority, role));
+    }
+}
+
+#[test]
+fn granted_roles_authorize_their_instructions_until_revoked() {
+    let mut fixture = Fixture::new();
+    let (authority, operator) = (fixture.authority, fixture.operator);
+
+    assert_eq!(fixture.update_config(operator).unwrap_err(), error(SolFlexError::Unauthorized));
+    fixture.grant_role(authority, operator, Role::Admin).unwrap();
+    assert_eq!(
+        fixture.grant_role(authority, operator, Role::Admin).unwrap_err(),
+        error(SolFlexError::RoleAlreadyGranted)
+    );
+    fixture.update_config(operator).unwrap();
+    assert_eq!(fixture.config().min_reflection_pool, 5_000);
+
+    // Roles are managed by the authority alone, not by Admins.
+    let other = Pubkey::new_unique();
+    assert_eq!(fixture.grant_role(operator, other, Role::Admin).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(fixture.revoke_role(operator, operator, Role::Admin).unwrap_err(), error(SolFlexError::Unauthorized));
+
+    fixture.revoke_role(authority, operator, Role::Admin).unwrap();
+    assert!(!fixture.config().has_role(operator, Role::Admin));
+    assert_eq!(fixture.update_config(operator).unwrap_err(), error(SolFlexError::Unauthorized));
+    assert_eq!(
+        fixture.revoke_role(authority, operator, Role::Admin).unwrap_err(),
+        error(SolFlexError::RoleNotGranted)
+    );
+}
+
+#[test]
+fn the_authority_needs_a_proposal_once_a_multisig_governs() {
+    let mut fixture = Fixture::new();
+    let mut config = fixture.config();
+    config.set_multisig(vec![fixture.authority, Pubkey::new_unique()], 2).unwrap();
+    fixture.ledger.add_state(fixture.config, sol_flex::ID, &config, 8 + Config::INIT_SPACE);
+
+    assert_eq!(fixture.update_config(fixture.authority).unwrap_err(), error(SolFlexError::ProposalRequired));
+    assert_eq!(
+        fixture.grant_role(fixture.authority, fixture.operator, Role::Admin).unwrap_err(),
+        error(SolFlexError::ProposalRequired)
+    );
+    assert_eq!(fixture.config().min_reflection_pool, config.min_reflection_pool);
+}
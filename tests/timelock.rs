@@ -0,0 +1,171 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::SetDistributionConfigParams;
+use sol_flex::state::{
+    AdminAction, Config, ConfigChange, CrankReward, DistributionConfig, DistributionMode, DustPolicy,
+    PendingConfigChange, Proposal, ReflectThrottle,
+};
+
+fn distribution() -> DistributionConfig {
+    DistributionConfig::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        255,
+    )
+}
+
+// Params that leave every setting of `distribution` as it is.
+fn unchanged(distribution: &DistributionConfig) -> SetDistributionConfigParams {
+    SetDistributionConfigParams {
+        token_mint: distribution.token_mint,
+        fee_vault: distribution.fee_vault,
+        limit: distribution.limit,
+        distribution_mode: distribution.distribution_mode,
+        throttle: distribution.throttle,
+        reflection_rate: distribution.reflection_rate,
+        burn_rate: distribution.burn_rate,
+        project_rate: distribution.project_rate,
+        project_account: distribution.project_account,
+        dev_fee_rate: distribution.dev_fee_rate,
+        dev_account: distribution.dev_account,
+        permissionless_crank: distribution.permissionless_crank,
+        crank_reward: distribution.crank_reward,
+        max_crank_reward_bps: distribution.max_crank_reward_bps,
+        dust_policy: distribution.dust_policy,
+        auto_create_accounts: distribution.auto_create_accounts,
+        max_rent_per_batch: distribution.max_rent_per_batch,
+    }
+}
+
+#[test]
+fn direct_changes_are_rejected_while_timelocked() {
+    common::setup();
+    let distribution = distribution();
+    let edits: Vec<fn(&mut SetDistributionConfigParams)> = vec![
+        |p| p.fee_vault = Pubkey::new_unique(),
+        |p| p.reflection_rate += 1,
+        |p| p.burn_rate += 1,
+        |p| p.project_rate += 1,
+        |p| p.project_account = Pubkey::new_unique(),
+        |p| p.dev_fee_rate += 1,
+        |p| p.dev_account = Pubkey::new_unique(),
+        |p| p.distribution_mode = DistributionMode::Accumulator,
+        |p| p.throttle = ReflectThrottle::DrainAll,
+        |p| p.permissionless_crank = !p.permissionless_crank,
+        |p| p.crank_reward = CrankReward::Flat(1),
+        |p| p.max_crank_reward_bps += 1,
+        |p| p.dust_policy = DustPolicy::RollOver(1_000),
+        |p| p.auto_create_accounts = !p.auto_create_accounts,
+        |p| p.max_rent_per_batch += 1,
+    ];
+
+    for edit in edits {
+        let mut params = unchanged(&distribution);
+        edit(&mut params);
+        assert_eq!(
+            params.require_untimelocked(&distribution, 3_600).unwrap_err(),
+            SolFlexError::TimelockRequired.into()
+        );
+        params.require_untimelocked(&distribution, 0).unwrap();
+    }
+
+    let mut params = unchanged(&distribution);
+    params.limit += 1;
+    params.require_untimelocked(&distribution, 3_600).unwrap();
+}
+
+#[test]
+fn queued_settings_are_validated_against_the_distribution() {
+    common::setup();
+    let mut distribution = distribution();
+    distribution.distribution_mode = DistributionMode::Equal;
+
+    let crank = ConfigChange::CrankSettings {
+        permissionless_crank: true,
+        crank_reward: CrankReward::BasisPoints(10),
+        max_crank_reward_bps: 100,
+    };
+    crank.validate().unwrap();
+    assert_eq!(
+        crank.apply_to_distribution(&mut distribution).unwrap_err(),
+        SolFlexError::InvalidParameters.into()
+    );
+
+    ConfigChange::DistributionMode(DistributionMode::Proportional)
+        .apply_to_distribution(&mut distribution)
+        .unwrap();
+    crank.apply_to_distribution(&mut distribution).unwrap();
+    assert!(distribution.permissionless_crank);
+
+    let throttle = ConfigChange::Throttle(ReflectThrottle::BasisPoints(0));
+    assert_eq!(throttle.validate().unwrap_err(), SolFlexError::InvalidParameters.into());
+
+    ConfigChange::AccountCreation { auto_create_accounts: true, max_rent_per_batch: 5_000_000 }
+        .apply_to_distribution(&mut distribution)
+        .unwrap();
+    assert!(distribution.auto_create_accounts);
+    assert_eq!(distribution.max_rent_per_batch, 5_000_000);
+}
+
+#[test]
+fn cancelling_a_queued_change_needs_the_multisig_threshold() {
+    common::setup();
+    let mut ledger = Ledger::new();
+    let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    for signer in signers {
+        ledger.add_wallet(signer, 1_000_000_000);
+    }
+
+    let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+    let mut config_state = Config::new(signers[0], config_bump);
+    config_state.set_multisig(signers.to_vec(), 2).unwrap();
+    ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+    let (pending_change, pending_bump) =
+        Pubkey::find_program_address(&[PendingConfigChange::SEED_PREFIX, 0u64.to_le_bytes().as_ref()], &sol_flex::ID);
+    let change = ConfigChange::TimelockDelay(0);
+    let pending_state = PendingConfigChange::new(0, config, change, signers[0], 3_600, pending_bump).unwrap();
+    ledger.add_state(pending_change, sol_flex::ID, &pending_state, 8 + PendingConfigChange::INIT_SPACE);
+
+    let proposal = Pubkey::new_unique();
+    let params_hash = Proposal::hash_params(&[config, pending_change], &0u64).unwrap();
+    let mut proposal_state =
+        Proposal::new(0, signers[1], AdminAction::CancelConfigChange, params_hash, config_state.multisig_nonce, 255);
+    ledger.add_state(proposal, sol_flex::ID, &proposal_state, 8 + Proposal::INIT_SPACE);
+
+    let cancel = |proposal: Option<Pubkey>| Instruction {
+        program_id: sol_flex::ID,
+        accounts: sol_flex::accounts::CancelConfigChange {
+            config,
+            pending_change,
+            queued_by: signers[0],
+            proposal,
+            authority: signers[1],
+        }
+        .to_account_metas(None),
+        data: sol_flex::instruction::CancelConfigChange {}.data(),
+    };
+
+    // A single signer can no longer cancel on their own.
+    assert_eq!(ledger.process(&cancel(None)).unwrap_err(), error(SolFlexError::ProposalRequired));
+    assert_eq!(ledger.process(&cancel(Some(proposal))).unwrap_err(), error(SolFlexError::ThresholdNotMet));
+
+    proposal_state.approve(&config_state, signers[2]).unwrap();
+    ledger.add_state(proposal, sol_flex::ID, &proposal_state, 8 + Proposal::INIT_SPACE);
+    let rent = ledger.lamports(&pending_change);
+    let before = ledger.lamports(&signers[0]);
+    ledger.process(&cancel(Some(proposal))).unwrap();
+    assert_eq!(ledger.lamports(&pending_change), 0);
+    assert_eq!(ledger.lamports(&signers[0]), before + rent);
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
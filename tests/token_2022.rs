@@ -0,0 +1,260 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use common::runtime::Ledger;
+use sol_flex::errors::SolFlexError;
+use sol_flex::instructions::{DepositFeesParams, ReflectParams};
+use sol_flex::state::{
+    Config, CyclePhase, DistributionConfig, DistributionMode, GlobalTokenPools, ReflectThrottle, UserPreferences,
+};
+
+const FEE_BPS: u16 = 200;
+
+// A Token-2022 distribution whose mint withholds 2% of every transfer, with two holders
+// of 495,000 each and a 10,000 reflection pool.
+struct Fixture {
+    ledger: Ledger,
+    authority: Pubkey,
+    config: Pubkey,
+    distribution: Pubkey,
+    token_mint: Pubkey,
+    fee_vault: Pubkey,
+    dev_token_account: Pubkey,
+    global_pools: Pubkey,
+    holders: Vec<(Pubkey, Pubkey)>, // Preferences, token account
+}
+
+impl Fixture {
+    fn new(min_reflection_per_account: u64) -> Self {
+        common::setup();
+        let mut ledger = Ledger::new();
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        ledger.add_wallet(authority, 1_000_000_000);
+
+        let (config, config_bump) = Pubkey::find_program_address(&[Config::SEED_PREFIX], &sol_flex::ID);
+        let mut config_state = Config::new(authority, config_bump);
+        config_state.min_reflection_pool = 1;
+        config_state.min_reflection_per_account = min_reflection_per_account;
+        config_state.holder_count = 2;
+        ledger.add_state(config, sol_flex::ID, &config_state, 8 + Config::INIT_SPACE);
+
+        let (distribution, bump) =
+            Pubkey::find_program_address(&[DistributionConfig::SEED_PREFIX, token_mint.as_ref()], &sol_flex::ID);
+        ledger.add_fee_mint(token_mint, 6, 1_000_000, FEE_BPS, u64::MAX, distribution);
+        let fee_vault = Pubkey::new_unique();
+        let dev_token_account = Pubkey::new_unique();
+        let mut distribution_state =
+            DistributionConfig::new(token_mint, fee_vault, Pubkey::new_unique(), dev_token_account, bump);
+        distribution_state.distribution_mode = DistributionMode::Proportional;
+        distribution_state.throttle = ReflectThrottle::DrainAll;
+        distribution_state.reflection_pool = 10_000;
+        ledger.add_state(distribution, sol_flex::ID, &distribution_state, 8 + DistributionConfig::INIT_SPACE);
+        ledger.add_fee_token_account(fee_vault, token_mint, distribution, 10_000, 0);
+        ledger.add_fee_token_account(dev_token_account, token_mint, Pubkey::new_unique(), 0, 0);
+
+        let (global_pools, pools_bump) = Pubkey::find_program_address(&[GlobalTokenPools::SEED_PREFIX], &sol_flex::ID);
+        ledger.add_state(
+            global_pools,
+            sol_flex::ID,
+            &GlobalTokenPools::new(authority, pools_bump),
+            8 + GlobalTokenPools::INIT_SPACE,
+        );
+
+        let holders = (0..2)
+            .map(|index| {
+                let owner = Pubkey::new_unique();
+                ledger.add_wallet(owner, 1_000_000_000);
+                let (preferences, _) =
+                    Pubkey::find_program_address(&[UserPreferences::SEED_PREFIX, owner.as_ref()], &sol_flex::ID);
+                ledger.add_state(
+                    preferences,
+                    sol_flex::ID,
+                    &UserPreferences::new(owner, index),
+                    8 + UserPreferences::INIT_SPACE,
+                );
+                let token_account = Pubkey::new_unique();
+                ledger.add_fee_token_account(token_account, token_mint, owner, 495_000, 0);
+                (preferences, token_account)
+            })
+            .collect();
+
+        Self {
+            ledger,
+            authority,
+            config,
+            distribution,
+            token_mint,
+            fee_vault,
+            dev_token_account,
+            global_pools,
+            holders,
+        }
+    }
+
+    fn reflect(&mut self) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::Reflect {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            dev_token_account: self.dev_token_account,
+            token_mint: self.token_mint,
+            global_pools: self.global_pools,
+            authority: self.authority,
+            crank_token_account: None,
+            rent_reserve: None,
+            associated_token_program: None,
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        for (preferences, token_account) in &self.holders {
+            accounts.push(AccountMeta::new(*preferences, false));
+            accounts.push(AccountMeta::new(*token_account, false));
+        }
+        let data = sol_flex::instruction::Reflect {
+            params: ReflectParams { pair_count: self.holders.len() as u16, swap_routes: Vec::new() },
+        }
+        .data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+
+    fn harvest(&mut self) -> std::result::Result<(), ProgramError> {
+        let mut accounts = sol_flex::accounts::HarvestWithheldFees {
+            config: self.config,
+            distribution_config: self.distribution,
+            fee_vault: self.fee_vault,
+            token_mint: self.token_mint,
+            authority: self.authority,
+            token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(self.holders.iter().map(|(_, token_account)| AccountMeta::new(*token_account, false)));
+        let data = sol_flex::instruction::HarvestWithheldFees {}.data();
+        self.ledger.process(&Instruction { program_id: sol_flex::ID, accounts, data })
+    }
+}
+
+fn error(error: SolFlexError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+#[test]
+fn fee_mints_pay_holders_net_of_the_withheld_transfer_fee() {
+    let mut fixture = Fixture::new(1);
+
+    fixture.reflect().unwrap();
+    assert_eq!(fixture.ledger.state::<DistributionConfig>(&fixture.distribution).cycle_phase, CyclePhase::Payout);
+    fixture.reflect().unwrap();
+
+    // Each holder's 4,950 share leaves the vault gross; 2% stays withheld on their account.
+    for (_, token_account) in &fixture.holders {
+        assert_eq!(fixture.ledger.token_amount(token_account), 495_000 + 4_851);
+        assert_eq!(fixture.ledger.withheld_amount(token_account), 99);
+    }
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 100);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.cycle_phase, CyclePhase::Idle);
+    assert_eq!(distribution.cycle_distributed, 9_900);
+    assert_eq!(distribution.reflection_pool, 100);
+}
+
+#[test]
+fn the_per_account_minimum_applies_to_the_net_payout() {
+    // 4,950 gross clears a 4,900 minimum, but only 4,851 would arrive.
+    let mut fixture = Fixture::new(4_900);
+
+    fixture.reflect().unwrap();
+    fixture.reflect().unwrap();
+    for (_, token_account) in &fixture.holders {
+        assert_eq!(fixture.ledger.token_amount(token_account), 495_000);
+    }
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 10_000);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.cycle_distributed, 0);
+    assert_eq!(distribution.reflection_pool, 10_000);
+}
+
+#[test]
+fn withheld_fees_are_harvested_into_the_vault_and_split() {
+    let mut fixture = Fixture::new(1);
+    let mut distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    distribution.reflection_rate = 7_000;
+    distribution.burn_rate = 2_000;
+    distribution.project_rate = 500;
+    distribution.dev_fee_rate = 500;
+    fixture
+        .ledger
+        .add_state(fixture.distribution, sol_flex::ID, &distribution, 8 + DistributionConfig::INIT_SPACE);
+    for (index, (_, token_account)) in fixture.holders.clone().into_iter().enumerate() {
+        let withheld = 100 * (index as u64 + 1);
+        let owner = Pubkey::new_unique();
+        fixture.ledger.add_fee_token_account(token_account, fixture.token_mint, owner, 495_000, withheld);
+    }
+
+    // The harvest needs the distribution to be the mint's withdraw-withheld authority.
+    fixture.ledger.add_fee_mint(fixture.token_mint, 6, 1_000_000, FEE_BPS, u64::MAX, Pubkey::new_unique());
+    assert_eq!(fixture.harvest().unwrap_err(), error(SolFlexError::InvalidConfig));
+    fixture.ledger.add_fee_mint(fixture.token_mint, 6, 1_000_000, FEE_BPS, u64::MAX, fixture.distribution);
+
+    // 300 withheld: 210 reflected, 60 burned, 15 each to the project and dev pools.
+    fixture.harvest().unwrap();
+    for (_, token_account) in &fixture.holders {
+        assert_eq!(fixture.ledger.withheld_amount(token_account), 0);
+    }
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 10_000 + 300 - 60);
+    assert_eq!(fixture.ledger.mint_supply(&fixture.token_mint), 1_000_000 - 60);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.reflection_pool, 10_210);
+    assert_eq!((distribution.project_pool, distribution.dev_pool), (15, 15));
+    assert_eq!(distribution.total_burned, 60);
+    assert_eq!(distribution.total_fees, 300);
+
+    // Nothing is left withheld, so a second harvest has nothing to record.
+    assert_eq!(fixture.harvest().unwrap_err(), error(SolFlexError::InsufficientFunds));
+}
+
+#[test]
+fn deposits_record_only_what_reaches_the_vault() {
+    let mut fixture = Fixture::new(1);
+    let mut config: Config = fixture.ledger.state(&fixture.config);
+    config.add_depositor(fixture.authority).unwrap();
+    fixture.ledger.add_state(fixture.config, sol_flex::ID, &config, 8 + Config::INIT_SPACE);
+    let mut distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    distribution.reflection_rate = 10_000;
+    distribution.burn_rate = 0;
+    distribution.project_rate = 0;
+    distribution.dev_fee_rate = 0;
+    fixture
+        .ledger
+        .add_state(fixture.distribution, sol_flex::ID, &distribution, 8 + DistributionConfig::INIT_SPACE);
+    let source = Pubkey::new_unique();
+    fixture.ledger.add_fee_token_account(source, fixture.token_mint, fixture.authority, 5_000, 0);
+
+    // 2% of the 1,000 sent is withheld on the vault, so only 980 is recorded.
+    fixture
+        .ledger
+        .process(&Instruction {
+            program_id: sol_flex::ID,
+            accounts: sol_flex::accounts::DepositFees {
+                config: fixture.config,
+                distribution_config: fixture.distribution,
+                fee_vault: fixture.fee_vault,
+                token_mint: fixture.token_mint,
+                depositor_token_account: source,
+                depositor: fixture.authority,
+                token_program: spl_token_2022::ID,
+            }
+            .to_account_metas(None),
+            data: sol_flex::instruction::DepositFees { params: DepositFeesParams { amount: 1_000 } }.data(),
+        })
+        .unwrap();
+    assert_eq!(fixture.ledger.token_amount(&fixture.fee_vault), 10_980);
+    assert_eq!(fixture.ledger.withheld_amount(&fixture.fee_vault), 20);
+    let distribution: DistributionConfig = fixture.ledger.state(&fixture.distribution);
+    assert_eq!(distribution.total_fees, 980);
+    assert_eq!(distribution.reflection_pool, 10_980);
+}